@@ -3,7 +3,9 @@ use ecdsa::SigningKey;
 use k256::Secp256k1;
 use primeorder::elliptic_curve::{ops::MulByGenerator, CurveArithmetic, Field, ProjectivePoint};
 use rand_core::OsRng;
-use tiny_curve::{TinyCurve16, TinyCurve32, TinyCurve64};
+use tiny_curve::{
+    msm, msm_vartime, FieldVec, PrecomputedBase, TinyCurve16, TinyCurve32, TinyCurve64,
+};
 
 fn bench_arithmetic(c: &mut Criterion) {
     let mut group = c.benchmark_group("arithmetic");
@@ -16,6 +18,14 @@ fn bench_arithmetic(c: &mut Criterion) {
         )
     });
 
+    group.bench_function("Curve16, mul_by_generator (precomputed)", |b| {
+        b.iter_batched(
+            || <TinyCurve16 as CurveArithmetic>::Scalar::random(&mut OsRng),
+            |scalar| PrecomputedBase::<TinyCurve16>::generator().mul(&scalar),
+            BatchSize::SmallInput,
+        )
+    });
+
     group.bench_function("Curve32, mul_by_generator", |b| {
         b.iter_batched(
             || <TinyCurve32 as CurveArithmetic>::Scalar::random(&mut OsRng),
@@ -24,6 +34,14 @@ fn bench_arithmetic(c: &mut Criterion) {
         )
     });
 
+    group.bench_function("Curve32, mul_by_generator (precomputed)", |b| {
+        b.iter_batched(
+            || <TinyCurve32 as CurveArithmetic>::Scalar::random(&mut OsRng),
+            |scalar| PrecomputedBase::<TinyCurve32>::generator().mul(&scalar),
+            BatchSize::SmallInput,
+        )
+    });
+
     group.bench_function("Curve64, mul_by_generator", |b| {
         b.iter_batched(
             || <TinyCurve64 as CurveArithmetic>::Scalar::random(&mut OsRng),
@@ -32,6 +50,14 @@ fn bench_arithmetic(c: &mut Criterion) {
         )
     });
 
+    group.bench_function("Curve64, mul_by_generator (precomputed)", |b| {
+        b.iter_batched(
+            || <TinyCurve64 as CurveArithmetic>::Scalar::random(&mut OsRng),
+            |scalar| PrecomputedBase::<TinyCurve64>::generator().mul(&scalar),
+            BatchSize::SmallInput,
+        )
+    });
+
     group.bench_function("k256, mul_by_generator", |b| {
         b.iter_batched(
             || <Secp256k1 as CurveArithmetic>::Scalar::random(&mut OsRng),
@@ -43,6 +69,175 @@ fn bench_arithmetic(c: &mut Criterion) {
     group.finish()
 }
 
+fn bench_field(c: &mut Criterion) {
+    let mut group = c.benchmark_group("field");
+
+    group.bench_function("Curve16, mul", |b| {
+        b.iter_batched(
+            || {
+                (
+                    <TinyCurve16 as CurveArithmetic>::Scalar::random(&mut OsRng),
+                    <TinyCurve16 as CurveArithmetic>::Scalar::random(&mut OsRng),
+                )
+            },
+            |(x, y)| x * y,
+            BatchSize::SmallInput,
+        )
+    });
+
+    group.bench_function("Curve32, mul", |b| {
+        b.iter_batched(
+            || {
+                (
+                    <TinyCurve32 as CurveArithmetic>::Scalar::random(&mut OsRng),
+                    <TinyCurve32 as CurveArithmetic>::Scalar::random(&mut OsRng),
+                )
+            },
+            |(x, y)| x * y,
+            BatchSize::SmallInput,
+        )
+    });
+
+    group.bench_function("Curve64, mul", |b| {
+        b.iter_batched(
+            || {
+                (
+                    <TinyCurve64 as CurveArithmetic>::Scalar::random(&mut OsRng),
+                    <TinyCurve64 as CurveArithmetic>::Scalar::random(&mut OsRng),
+                )
+            },
+            |(x, y)| x * y,
+            BatchSize::SmallInput,
+        )
+    });
+
+    group.bench_function("Curve16, invert", |b| {
+        b.iter_batched(
+            || <TinyCurve16 as CurveArithmetic>::Scalar::random(&mut OsRng),
+            |scalar| Field::invert(&scalar),
+            BatchSize::SmallInput,
+        )
+    });
+
+    group.bench_function("Curve16, invert_vartime", |b| {
+        b.iter_batched(
+            || <TinyCurve16 as CurveArithmetic>::Scalar::random(&mut OsRng),
+            |scalar| scalar.invert_vartime(),
+            BatchSize::SmallInput,
+        )
+    });
+
+    group.bench_function("Curve32, invert", |b| {
+        b.iter_batched(
+            || <TinyCurve32 as CurveArithmetic>::Scalar::random(&mut OsRng),
+            |scalar| Field::invert(&scalar),
+            BatchSize::SmallInput,
+        )
+    });
+
+    group.bench_function("Curve32, invert_vartime", |b| {
+        b.iter_batched(
+            || <TinyCurve32 as CurveArithmetic>::Scalar::random(&mut OsRng),
+            |scalar| scalar.invert_vartime(),
+            BatchSize::SmallInput,
+        )
+    });
+
+    group.bench_function("Curve64, invert", |b| {
+        b.iter_batched(
+            || <TinyCurve64 as CurveArithmetic>::Scalar::random(&mut OsRng),
+            |scalar| Field::invert(&scalar),
+            BatchSize::SmallInput,
+        )
+    });
+
+    group.bench_function("Curve64, invert_vartime", |b| {
+        b.iter_batched(
+            || <TinyCurve64 as CurveArithmetic>::Scalar::random(&mut OsRng),
+            |scalar| scalar.invert_vartime(),
+            BatchSize::SmallInput,
+        )
+    });
+
+    group.finish()
+}
+
+fn bench_field_vec(c: &mut Criterion) {
+    let mut group = c.benchmark_group("field_vec");
+
+    let xs = (0..4096)
+        .map(|_| <TinyCurve16 as CurveArithmetic>::Scalar::random(&mut OsRng))
+        .collect::<Vec<_>>();
+    let ys = (0..4096)
+        .map(|_| <TinyCurve16 as CurveArithmetic>::Scalar::random(&mut OsRng))
+        .collect::<Vec<_>>();
+    let (xv, yv) = (FieldVec::from_elements(&xs), FieldVec::from_elements(&ys));
+
+    group.bench_function("Curve16, 4096 muls, elements", |b| {
+        b.iter(|| {
+            xs.iter()
+                .zip(ys.iter())
+                .map(|(x, y)| *x * y)
+                .collect::<Vec<_>>()
+        })
+    });
+
+    group.bench_function("Curve16, 4096 muls, FieldVec", |b| b.iter(|| &xv * &yv));
+
+    let xs = (0..4096)
+        .map(|_| <TinyCurve32 as CurveArithmetic>::Scalar::random(&mut OsRng))
+        .collect::<Vec<_>>();
+    let ys = (0..4096)
+        .map(|_| <TinyCurve32 as CurveArithmetic>::Scalar::random(&mut OsRng))
+        .collect::<Vec<_>>();
+    let (xv, yv) = (FieldVec::from_elements(&xs), FieldVec::from_elements(&ys));
+
+    group.bench_function("Curve32, 4096 muls, elements", |b| {
+        b.iter(|| {
+            xs.iter()
+                .zip(ys.iter())
+                .map(|(x, y)| *x * y)
+                .collect::<Vec<_>>()
+        })
+    });
+
+    group.bench_function("Curve32, 4096 muls, FieldVec", |b| b.iter(|| &xv * &yv));
+
+    group.finish()
+}
+
+fn bench_msm(c: &mut Criterion) {
+    let mut group = c.benchmark_group("msm");
+
+    let terms = (0..1000)
+        .map(|_| {
+            (
+                ProjectivePoint::<TinyCurve64>::mul_by_generator(
+                    &<TinyCurve64 as CurveArithmetic>::Scalar::random(&mut OsRng),
+                ),
+                <TinyCurve64 as CurveArithmetic>::Scalar::random(&mut OsRng),
+            )
+        })
+        .collect::<Vec<_>>();
+
+    group.bench_function("Curve64, 1000 terms, naive", |b| {
+        b.iter(|| {
+            terms
+                .iter()
+                .map(|(point, scalar)| *point * scalar)
+                .sum::<ProjectivePoint<TinyCurve64>>()
+        })
+    });
+
+    group.bench_function("Curve64, 1000 terms, msm", |b| b.iter(|| msm(&terms)));
+
+    group.bench_function("Curve64, 1000 terms, msm_vartime", |b| {
+        b.iter(|| msm_vartime(&terms))
+    });
+
+    group.finish()
+}
+
 fn bench_ecdsa(c: &mut Criterion) {
     let mut group = c.benchmark_group("ECDSA");
 
@@ -85,6 +280,13 @@ fn bench_ecdsa(c: &mut Criterion) {
     group.finish()
 }
 
-criterion_group!(benches, bench_arithmetic, bench_ecdsa);
+criterion_group!(
+    benches,
+    bench_arithmetic,
+    bench_field,
+    bench_field_vec,
+    bench_msm,
+    bench_ecdsa
+);
 
 criterion_main!(benches);
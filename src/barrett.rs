@@ -0,0 +1,197 @@
+//! Constant-time Barrett reduction for moduli that fit into 32 bits.
+//!
+//! For such moduli the product of two reduced numbers fits into 64 bits,
+//! and a single-word precomputed constant `floor(2^64 / m)` gives a quotient estimate
+//! that is off by at most one, so only one (constant-time) correction step is needed.
+
+use primeorder::elliptic_curve::subtle::{Choice, ConditionallySelectable};
+
+/// Precomputed constants for the reduction modulo `modulus`.
+#[derive(Copy, Clone, Debug)]
+pub struct Barrett {
+    modulus: u64,
+    mu: u64,
+}
+
+impl Barrett {
+    /// Pre-calculates the constants for a known modulus.
+    pub const fn new(modulus: u64) -> Self {
+        debug_assert!(modulus > 1 && modulus <= u32::MAX as u64);
+        Self {
+            modulus,
+            // `2^64 / modulus` fits into a `u64` since `modulus > 1`.
+            mu: ((1u128 << u64::BITS) / modulus as u128) as u64,
+        }
+    }
+}
+
+/// Calculates the remainder of `x` modulo the modulus `barrett` was created for.
+#[inline(always)]
+pub fn rem_with_barrett(x: u64, barrett: &Barrett) -> u64 {
+    // Since `mu <= 2^64 / m`, the estimate is at most `x / m`,
+    // and since `x * (2^64 / m - mu) / 2^64 < x / 2^64 < 1`, it is smaller by at most one.
+    let q = (((x as u128) * (barrett.mu as u128)) >> u64::BITS) as u64;
+    let r = x.wrapping_sub(q.wrapping_mul(barrett.modulus));
+    // `r < 2m` here, so one conditional subtraction suffices.
+    let (r_reduced, borrow) = r.overflowing_sub(barrett.modulus);
+    u64::conditional_select(&r_reduced, &r, Choice::from(borrow as u8))
+}
+
+#[cfg(all(feature = "alloc", not(feature = "montgomery16")))]
+/// Precomputed constants for the reduction modulo a `modulus` below `2^16`
+/// with all the values in 32-bit lanes (and 64-bit products),
+/// so that loops over it can be auto-vectorized.
+#[derive(Copy, Clone, Debug)]
+pub struct BarrettLanes {
+    modulus: u32,
+    mu: u32,
+}
+
+#[cfg(all(feature = "alloc", not(feature = "montgomery16")))]
+impl BarrettLanes {
+    /// Pre-calculates the constants for a known modulus.
+    pub const fn new(modulus: u64) -> Self {
+        debug_assert!(modulus > 1 && modulus <= u16::MAX as u64);
+        Self {
+            modulus: modulus as u32,
+            // `2^32 / modulus` fits into a `u32` since `modulus > 1`.
+            mu: ((1u64 << u32::BITS) / modulus) as u32,
+        }
+    }
+}
+
+#[cfg(all(feature = "alloc", not(feature = "montgomery16")))]
+/// Calculates the remainder of `x` modulo the modulus `barrett` was created for.
+#[inline(always)]
+pub fn rem_with_barrett_lanes(x: u32, barrett: &BarrettLanes) -> u32 {
+    // Same as in `rem_with_barrett()`, the estimate is smaller than `x / m` by at most one.
+    let q = ((x as u64 * barrett.mu as u64) >> u32::BITS) as u32;
+    let r = x.wrapping_sub(q.wrapping_mul(barrett.modulus));
+    // `r < 2m`, so if `r < m`, the subtraction wraps around to a value greater than `r`.
+    r.min(r.wrapping_sub(barrett.modulus))
+}
+
+#[cfg(all(feature = "alloc", not(feature = "montgomery32")))]
+/// Precomputed constants for the reduction modulo a `modulus` in `(2^31, 2^32)`
+/// with all the multiplications being 32x32-bit ones (in 64-bit lanes),
+/// so that loops over it can be auto-vectorized.
+#[derive(Copy, Clone, Debug)]
+pub struct BarrettWideLanes {
+    // Keeping the constants 32-bit lets the compiler know that the products are 32x32-bit ones.
+    modulus: u32,
+    /// `floor(2^64 / modulus) - 2^32` (since `modulus > 2^31`, it fits into 32 bits).
+    mu_lo: u32,
+}
+
+#[cfg(all(feature = "alloc", not(feature = "montgomery32")))]
+impl BarrettWideLanes {
+    /// Pre-calculates the constants for a known modulus.
+    pub const fn new(modulus: u64) -> Self {
+        assert!(modulus > 1 << 31 && modulus <= u32::MAX as u64);
+        Self {
+            modulus: modulus as u32,
+            mu_lo: ((1u128 << u64::BITS) / modulus as u128 - (1u128 << u32::BITS)) as u32,
+        }
+    }
+}
+
+#[cfg(all(feature = "alloc", not(feature = "montgomery32")))]
+/// Returns `x` if `x < modulus`, and `x - modulus` otherwise, for `x < 2^63`.
+#[inline(always)]
+fn sub_if_not_less(x: u64, modulus: u64) -> u64 {
+    // Unsigned 64-bit comparisons are not available in the baseline SIMD instruction sets,
+    // so the borrow is taken from the top bit.
+    let reduced = x.wrapping_sub(modulus);
+    let borrow_mask = (reduced >> (u64::BITS - 1)).wrapping_neg();
+    reduced.wrapping_add(modulus & borrow_mask)
+}
+
+#[cfg(all(feature = "alloc", not(feature = "montgomery32")))]
+/// Calculates the remainder of `x < modulus^2` modulo the modulus `barrett` was created for.
+#[inline(always)]
+pub fn rem_with_barrett_wide_lanes(x: u64, barrett: &BarrettWideLanes) -> u64 {
+    // The quotient estimate is `floor(x_hi * floor(2^64 / m) / 2^32)`, which is at most `x / m`.
+    // Dropping `x_lo` loses less than `2^32 / m < 2`, and the floor of `2^64 / m`
+    // less than `x_hi / 2^32 < 1`, so with the final floor the estimate is smaller by at most 3.
+    let modulus = barrett.modulus as u64;
+    let x_hi = x >> u32::BITS;
+    let q_lo = (x_hi * barrett.mu_lo as u64) >> u32::BITS;
+    let qm = (x_hi * modulus).wrapping_add(q_lo * modulus);
+    let mut r = x.wrapping_sub(qm);
+    // `r < 4m < 2^34`
+    for _ in 0..3 {
+        r = sub_if_not_less(r, modulus);
+    }
+    r
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{rem_with_barrett, Barrett};
+    use proptest::prelude::*;
+
+    proptest! {
+        #[test]
+        fn rem(x in any::<u64>(), m in any::<u32>()) {
+            let m = if m < 2 {
+                2
+            }
+            else {
+                m as u64
+            };
+
+            let expected = x % m;
+            let test = rem_with_barrett(x, &Barrett::new(m));
+            assert_eq!(test, expected);
+        }
+    }
+}
+
+#[cfg(all(test, feature = "alloc", not(feature = "montgomery16")))]
+mod tests_lanes {
+    use super::{rem_with_barrett_lanes, BarrettLanes};
+    use proptest::prelude::*;
+
+    proptest! {
+        #[test]
+        fn rem_lanes(x in any::<u32>(), m in any::<u16>()) {
+            let m = if m < 2 {
+                2
+            }
+            else {
+                m as u64
+            };
+
+            let expected = x as u64 % m;
+            let test = rem_with_barrett_lanes(x, &BarrettLanes::new(m));
+            assert_eq!(test as u64, expected);
+        }
+    }
+}
+
+#[cfg(all(test, feature = "alloc", not(feature = "montgomery32")))]
+mod tests_wide_lanes {
+    use super::{rem_with_barrett_wide_lanes, BarrettWideLanes};
+    use proptest::prelude::*;
+
+    #[test]
+    fn rem_wide_lanes_largest_error() {
+        // The quotient estimate is smaller than the quotient by 3 for these values
+        let (x, m) = (0x68b1f3c9ffffffff, 0x80068677);
+        assert_eq!(
+            rem_with_barrett_wide_lanes(x, &BarrettWideLanes::new(m)),
+            x % m
+        );
+    }
+
+    proptest! {
+        #[test]
+        fn rem_wide_lanes(x in any::<u64>(), m in (1u64 << 31) + 1..=u32::MAX as u64) {
+            let barrett = BarrettWideLanes::new(m);
+            // The estimate error is the largest for the values close to `m^2`
+            for x in [x % (m * m), m * m - 1 - x % (1 << 40)] {
+                assert_eq!(rem_with_barrett_wide_lanes(x, &barrett), x % m);
+            }
+        }
+    }
+}
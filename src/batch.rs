@@ -0,0 +1,106 @@
+//! Batch conversion of points to the affine form.
+
+use primeorder::{
+    elliptic_curve::{ops::Invert, subtle::CtOption, BatchNormalize},
+    AffinePoint, PrimeCurveParams, ProjectivePoint,
+};
+
+// The number of points normalized with a single inversion.
+// The intermediate values are stored on stack, so this is a compromise
+// between the number of inversions and the stack usage.
+const CHUNK_SIZE: usize = 64;
+
+/// Converts `points` to the affine form, writing the results to `affine`.
+///
+/// Uses one field inversion per up to 64 points instead of one per point.
+/// The identity is converted to [`AffinePoint::IDENTITY`].
+///
+/// Panics if the slices have different lengths.
+pub fn batch_normalize<C>(points: &[ProjectivePoint<C>], affine: &mut [AffinePoint<C>])
+where
+    C: PrimeCurveParams,
+    C::FieldElement: Invert<Output = CtOption<C::FieldElement>>,
+    ProjectivePoint<C>:
+        BatchNormalize<[ProjectivePoint<C>; CHUNK_SIZE], Output = [AffinePoint<C>; CHUNK_SIZE]>,
+{
+    assert_eq!(
+        points.len(),
+        affine.len(),
+        "The output must have the same length as the input"
+    );
+
+    for (points, affine) in points.chunks(CHUNK_SIZE).zip(affine.chunks_mut(CHUNK_SIZE)) {
+        // The padding is the identity, which does not affect the other points.
+        let mut chunk = [ProjectivePoint::<C>::IDENTITY; CHUNK_SIZE];
+        chunk[..points.len()].copy_from_slice(points);
+        let normalized = ProjectivePoint::<C>::batch_normalize(&chunk);
+        affine.copy_from_slice(&normalized[..affine.len()]);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use primeorder::{
+        elliptic_curve::{
+            group::Curve, ops::Invert, subtle::CtOption, BatchNormalize, CurveArithmetic, Field,
+        },
+        AffinePoint, PrimeCurveParams, ProjectivePoint,
+    };
+    use rand_core::OsRng;
+
+    use super::{batch_normalize, CHUNK_SIZE};
+    use crate::{TinyCurve16, TinyCurve32, TinyCurve64};
+
+    fn check_normalize<C>()
+    where
+        C: PrimeCurveParams,
+        C::FieldElement: Invert<Output = CtOption<C::FieldElement>>,
+        ProjectivePoint<C>: Curve<AffineRepr = AffinePoint<C>>
+            + BatchNormalize<[ProjectivePoint<C>; CHUNK_SIZE], Output = [AffinePoint<C>; CHUNK_SIZE]>,
+    {
+        // Check several chunk lengths, with the identity in the middle and at the end.
+        for len in [0, 1, 63, 64, 65, 150] {
+            let mut points = (0..len)
+                .map(|_| ProjectivePoint::<C>::GENERATOR * C::Scalar::random(&mut OsRng))
+                .collect::<Vec<_>>();
+            if len > 0 {
+                points[len / 2] = ProjectivePoint::<C>::IDENTITY;
+                points[len - 1] = ProjectivePoint::<C>::IDENTITY;
+            }
+
+            let mut affine = vec![AffinePoint::<C>::IDENTITY; len];
+            batch_normalize(&points, &mut affine);
+
+            let expected = points.iter().map(|p| p.to_affine()).collect::<Vec<_>>();
+            assert_eq!(affine, expected);
+        }
+    }
+
+    #[test]
+    fn normalize() {
+        check_normalize::<TinyCurve16>();
+        check_normalize::<TinyCurve32>();
+        check_normalize::<TinyCurve64>();
+    }
+
+    #[test]
+    fn invert() {
+        type F = <TinyCurve64 as CurveArithmetic>::Scalar;
+
+        let mut elements = (0..100).map(|_| F::random(&mut OsRng)).collect::<Vec<_>>();
+        elements[0] = F::ZERO;
+        elements[50] = F::ZERO;
+        elements[99] = F::ZERO;
+
+        let mut inverses = vec![F::ZERO; elements.len()];
+        F::batch_invert(&elements, &mut inverses);
+
+        let expected = elements
+            .iter()
+            .map(|x| Field::invert(x).unwrap_or(F::ZERO))
+            .collect::<Vec<_>>();
+        assert_eq!(inverses, expected);
+
+        F::batch_invert(&[], &mut []);
+    }
+}
@@ -0,0 +1,27 @@
+//! Writes the Wycheproof-format test vectors for all the curves into the given directory
+//! (the current one by default).
+//!
+//! Usage: `tiny-curve-wycheproof [OUTPUT_DIR]`
+
+use std::{env, fs, path::PathBuf, process::ExitCode};
+
+fn main() -> ExitCode {
+    let dir = PathBuf::from(env::args().nth(1).unwrap_or_else(|| ".".into()));
+
+    if let Err(err) = fs::create_dir_all(&dir) {
+        eprintln!("Failed to create {}: {err}", dir.display());
+        return ExitCode::FAILURE;
+    }
+
+    for (file_name, contents) in tiny_curve::wycheproof::test_vector_files() {
+        let path = dir.join(file_name);
+        let json = serde_json::to_string_pretty(&contents).expect("JSON values are serializable");
+        if let Err(err) = fs::write(&path, json + "\n") {
+            eprintln!("Failed to write {}: {err}", path.display());
+            return ExitCode::FAILURE;
+        }
+        println!("{}", path.display());
+    }
+
+    ExitCode::SUCCESS
+}
@@ -2,14 +2,37 @@ use bip32::{PrivateKeyBytes, PublicKeyBytes};
 use primeorder::elliptic_curve::{
     bigint::ArrayEncoding,
     generic_array::{typenum::Unsigned, GenericArray},
-    ops::{MulByGenerator, ReduceNonZero},
+    ops::MulByGenerator,
     sec1::{EncodedPoint, FromEncodedPoint, ModulusSize, ToEncodedPoint},
-    Curve, CurveArithmetic, NonZeroScalar, PublicKey, SecretKey,
+    Curve, CurveArithmetic, NonZeroScalar, PublicKey, ScalarPrimitive, SecretKey,
 };
 
 use crate::prime_field::ReprSizeTypenum;
 
+/// Converts the tweak `IL` into a scalar.
+///
+/// BIP32 parses all of `IL` as a 256-bit integer, which for the tiny curves
+/// would practically always be out of range. Instead, the leftmost bytes of `IL`
+/// (as many as there are in the curve order) are parsed as a big-endian integer,
+/// and, as in BIP32, the tweak is rejected if it is greater than or equal to the order.
+/// The callers are expected to proceed with the next child number in that case.
+fn tweak_to_scalar<C: CurveArithmetic>(tweak: &PrivateKeyBytes) -> Result<C::Scalar, bip32::Error> {
+    let order_repr = C::ORDER.to_be_byte_array();
+    let leading_zeros = order_repr.iter().take_while(|byte| **byte == 0).count();
+    let order_bytes = order_repr.len() - leading_zeros;
+
+    let mut repr = GenericArray::<u8, <C::Uint as ArrayEncoding>::ByteSize>::default();
+    repr[leading_zeros..].copy_from_slice(&tweak[..order_bytes]);
+    Option::<ScalarPrimitive<C>>::from(ScalarPrimitive::new(C::Uint::from_be_byte_array(repr)))
+        .map(C::Scalar::from)
+        .ok_or(bip32::Error::Crypto)
+}
+
 /// A newtype wrapper for [`elliptic_curve::SecretKey`] implementing [`bip32`] traits.
+///
+/// The child derivation returns [`bip32::Error::Crypto`] if the tweak is out of range
+/// or the child key is zero (the tweak is parsed from the leftmost bytes of `IL`,
+/// as many as there are in the curve order).
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct PrivateKeyBip32<C: CurveArithmetic>(SecretKey<C>);
 
@@ -32,6 +55,9 @@ impl<C: CurveArithmetic> From<PrivateKeyBip32<C>> for SecretKey<C> {
 }
 
 /// A newtype wrapper for [`elliptic_curve::PublicKey`] implementing [`bip32`] traits.
+///
+/// The child derivation returns [`bip32::Error::Crypto`] if the tweak is out of range
+/// or the child key is the identity.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct PublicKeyBip32<C: CurveArithmetic>(PublicKey<C>);
 
@@ -58,7 +84,6 @@ where
     C: Curve + CurveArithmetic,
     C::AffinePoint: ToEncodedPoint<C> + FromEncodedPoint<C>,
     C::FieldBytesSize: ModulusSize,
-    C::Scalar: ReduceNonZero<C::Uint>,
 {
     fn from_bytes(bytes: PublicKeyBytes) -> Result<Self, bip32::Error> {
         let bytes_len = bytes.len();
@@ -79,15 +104,7 @@ where
     }
 
     fn derive_child(&self, other: PrivateKeyBytes) -> Result<Self, bip32::Error> {
-        let bytes_len = other.len();
-        let repr = GenericArray::<u8, <C::Uint as ArrayEncoding>::ByteSize>::from_exact_iter(
-            other[bytes_len - <C::Uint as ArrayEncoding>::ByteSize::to_usize()..]
-                .iter()
-                .copied(),
-        )
-        .expect("slice length is correct");
-        let child_scalar = NonZeroScalar::<C>::reduce_nonzero(C::Uint::from_be_byte_array(repr));
-
+        let child_scalar = tweak_to_scalar::<C>(&other)?;
         let child_point =
             self.0.to_projective() + C::ProjectivePoint::mul_by_generator(&child_scalar);
         Ok(Self(
@@ -101,7 +118,6 @@ where
     C: Curve + CurveArithmetic,
     C::AffinePoint: ToEncodedPoint<C> + FromEncodedPoint<C>,
     C::FieldBytesSize: ModulusSize,
-    C::Scalar: ReduceNonZero<C::Uint>,
 {
     type PublicKey = PublicKeyBip32<C>;
 
@@ -122,15 +138,8 @@ where
     }
 
     fn derive_child(&self, other: PrivateKeyBytes) -> Result<Self, bip32::Error> {
-        let bytes_len = other.len();
-        let repr = GenericArray::<u8, <C::Uint as ArrayEncoding>::ByteSize>::from_exact_iter(
-            other[bytes_len - <C::Uint as ArrayEncoding>::ByteSize::to_usize()..]
-                .iter()
-                .copied(),
-        )
-        .expect("slice length is correct");
-        let child_scalar = NonZeroScalar::<C>::reduce_nonzero(C::Uint::from_be_byte_array(repr));
-        let derived_scalar = *self.0.to_nonzero_scalar().as_ref() + *child_scalar.as_ref();
+        let child_scalar = tweak_to_scalar::<C>(&other)?;
+        let derived_scalar = *self.0.to_nonzero_scalar().as_ref() + child_scalar;
 
         Option::<NonZeroScalar<C>>::from(NonZeroScalar::new(derived_scalar))
             .map(SecretKey::from)
@@ -149,7 +158,7 @@ mod tests {
     use primeorder::elliptic_curve::SecretKey;
     use rand_core::OsRng;
 
-    use crate::curve64::TinyCurve64;
+    use crate::{curve16::TinyCurve16, curve64::TinyCurve64};
 
     use super::{PrivateKeyBip32, PublicKeyBip32};
 
@@ -192,4 +201,50 @@ mod tests {
 
         assert_eq!(derived_from_sk.public_key(), derived_from_pk);
     }
+
+    /// Searches for a non-hardened child number for which `condition` holds for the tweak value,
+    /// checking along the way that the derivation fails exactly for the invalid children.
+    fn find_child_number(
+        sk: &PrivateKeyBip32<TinyCurve16>,
+        condition: impl Fn(u64) -> bool,
+    ) -> ChildNumber {
+        let order = crate::curve16::ORDER;
+        let secret = sk.as_ref().to_nonzero_scalar().to_u64();
+        let pk = sk.public_key();
+        let chain_code = [1u8; 32];
+
+        for index in 0..1 << 22 {
+            let child_number = ChildNumber(index);
+            let (tweak, _chain_code) = pk.derive_tweak(&chain_code, child_number).unwrap();
+
+            // The tweak is the leftmost 2 bytes of `IL` for `TinyCurve16`
+            let tweak_value = u64::from(u16::from_be_bytes([tweak[0], tweak[1]]));
+            if tweak_value < order && (secret + tweak_value) % order != 0 {
+                assert!(sk.derive_child(tweak).is_ok());
+            } else {
+                assert_eq!(sk.derive_child(tweak), Err(bip32::Error::Crypto));
+                assert_eq!(pk.derive_child(tweak), Err(bip32::Error::Crypto));
+            }
+
+            if condition(tweak_value) {
+                return child_number;
+            }
+        }
+        panic!("no matching child number found");
+    }
+
+    #[test]
+    fn out_of_range_tweak() {
+        // About 0.56% of the tweaks are out of range for `TinyCurve16`
+        let sk = PrivateKeyBip32::from(SecretKey::<TinyCurve16>::random(&mut OsRng));
+        find_child_number(&sk, |tweak| tweak >= crate::curve16::ORDER);
+    }
+
+    #[test]
+    fn zero_child() {
+        // Takes about `n` attempts for `TinyCurve16`
+        let sk = PrivateKeyBip32::from(SecretKey::<TinyCurve16>::random(&mut OsRng));
+        let secret = sk.as_ref().to_nonzero_scalar().to_u64();
+        find_child_number(&sk, |tweak| secret + tweak == crate::curve16::ORDER);
+    }
 }
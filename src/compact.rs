@@ -0,0 +1,345 @@
+//! Encodings with the size proportional to the size of the curve.
+
+use primeorder::{
+    elliptic_curve::{
+        point::{AffineCoordinates, DecompressPoint},
+        subtle::{Choice, ConditionallySelectable, ConstantTimeEq, CtOption},
+        CurveArithmetic, Field, NonZeroScalar, PrimeField, PublicKey, SecretKey,
+    },
+    AffinePoint, PrimeCurveParams, ProjectivePoint,
+};
+
+#[cfg(feature = "ecdsa")]
+use ::ecdsa::Signature;
+
+use crate::{
+    prime_field::{FieldElement, ReprSizeTypenum},
+    traits::{InternalConstants, Modulus, PrimeFieldConstants},
+    TinyCurve16, TinyCurve32, TinyCurve64,
+};
+
+/// Fixed-size encodings proportional to the size of the curve.
+///
+/// Field elements (including scalars) are encoded as big-endian integers
+/// of the size of their underlying integer type (2, 4, or 8 bytes).
+/// Points are encoded as SEC1 compressed points with the x-coordinate encoded the same way
+/// (3, 5, or 9 bytes), and the identity encoded as all zeros.
+/// Secret and public keys are encoded as their scalars and points respectively,
+/// and ECDSA signatures as the concatenation of the encodings of `r` and `s`.
+///
+/// Only canonical encodings are accepted when decoding.
+pub trait CompactEncoding: Sized {
+    /// The encoded representation.
+    type Bytes: AsRef<[u8]> + AsMut<[u8]> + Default + Copy;
+
+    /// Encodes the value.
+    fn to_compact_bytes(&self) -> Self::Bytes;
+
+    /// Decodes the value, failing if the encoding is not canonical.
+    fn from_compact_bytes(bytes: &Self::Bytes) -> CtOption<Self>;
+}
+
+macro_rules! impl_field_element {
+    ($t:ty) => {
+        impl<const M: u64> CompactEncoding for FieldElement<$t, M>
+        where
+            Modulus<$t, M>: PrimeFieldConstants<$t> + InternalConstants<$t>,
+        {
+            type Bytes = [u8; size_of::<$t>()];
+
+            fn to_compact_bytes(&self) -> Self::Bytes {
+                let repr = self.to_repr();
+                let repr = repr.as_ref();
+                let mut bytes = Self::Bytes::default();
+                bytes.copy_from_slice(&repr[repr.len() - size_of::<$t>()..]);
+                bytes
+            }
+
+            fn from_compact_bytes(bytes: &Self::Bytes) -> CtOption<Self> {
+                // `from_repr()` checks that the value is in range.
+                let mut repr = <Self as PrimeField>::Repr::default();
+                let repr_bytes = repr.as_mut();
+                let repr_len = repr_bytes.len();
+                repr_bytes[repr_len - bytes.len()..].copy_from_slice(bytes);
+                Self::from_repr(repr)
+            }
+        }
+    };
+}
+
+impl_field_element!(u16);
+impl_field_element!(u32);
+impl_field_element!(u64);
+
+fn point_to_compact_bytes<C>(point: &AffinePoint<C>, bytes: &mut [u8])
+where
+    C: PrimeCurveParams<FieldBytesSize = ReprSizeTypenum>,
+{
+    let is_identity = point.is_identity();
+    let x = point.x();
+    let x_len = bytes.len() - 1;
+    bytes[0] = 2 | point.y_is_odd().unwrap_u8();
+    bytes[1..].copy_from_slice(&x[x.len() - x_len..]);
+    for byte in bytes.iter_mut() {
+        byte.conditional_assign(&0, is_identity);
+    }
+}
+
+fn point_from_compact_bytes<C>(
+    bytes: &[u8],
+    x: CtOption<C::FieldElement>,
+) -> CtOption<AffinePoint<C>>
+where
+    C: PrimeCurveParams<FieldBytesSize = ReprSizeTypenum>,
+    AffinePoint<C>: DecompressPoint<C>,
+{
+    let is_identity = bytes
+        .iter()
+        .fold(Choice::from(1), |acc, byte| acc & byte.ct_eq(&0));
+
+    let tag = bytes[0];
+    let tag_is_valid = tag.ct_eq(&2) | tag.ct_eq(&3);
+    let y_is_odd = Choice::from(tag & 1);
+    let point = x.and_then(|x| AffinePoint::<C>::decompress(&x.to_repr(), y_is_odd));
+    let is_valid = (point.is_some() & tag_is_valid) | is_identity;
+
+    let point = AffinePoint::<C>::conditional_select(
+        &point.unwrap_or(AffinePoint::<C>::IDENTITY),
+        &AffinePoint::<C>::IDENTITY,
+        is_identity,
+    );
+    CtOption::new(point, is_valid)
+}
+
+macro_rules! impl_point {
+    ($curve:ty, $size:expr) => {
+        impl CompactEncoding for AffinePoint<$curve> {
+            type Bytes = [u8; $size];
+
+            fn to_compact_bytes(&self) -> Self::Bytes {
+                let mut bytes = Self::Bytes::default();
+                point_to_compact_bytes(self, &mut bytes);
+                bytes
+            }
+
+            fn from_compact_bytes(bytes: &Self::Bytes) -> CtOption<Self> {
+                let x_bytes = bytes[1..].try_into().expect("slice has the correct length");
+                let x = <$curve as PrimeCurveParams>::FieldElement::from_compact_bytes(&x_bytes);
+                point_from_compact_bytes::<$curve>(bytes, x)
+            }
+        }
+
+        impl CompactEncoding for ProjectivePoint<$curve> {
+            type Bytes = [u8; $size];
+
+            fn to_compact_bytes(&self) -> Self::Bytes {
+                self.to_affine().to_compact_bytes()
+            }
+
+            fn from_compact_bytes(bytes: &Self::Bytes) -> CtOption<Self> {
+                AffinePoint::<$curve>::from_compact_bytes(bytes).map(Self::from)
+            }
+        }
+    };
+}
+
+impl_point!(TinyCurve16, 3);
+impl_point!(TinyCurve32, 5);
+impl_point!(TinyCurve64, 9);
+
+macro_rules! impl_keys {
+    ($curve:ty, $size:expr) => {
+        impl CompactEncoding for SecretKey<$curve> {
+            type Bytes = [u8; $size];
+
+            fn to_compact_bytes(&self) -> Self::Bytes {
+                self.to_nonzero_scalar().to_compact_bytes()
+            }
+
+            fn from_compact_bytes(bytes: &Self::Bytes) -> CtOption<Self> {
+                type Scalar = <$curve as CurveArithmetic>::Scalar;
+                let scalar = Scalar::from_compact_bytes(bytes);
+                let is_some = scalar.is_some() & !scalar.unwrap_or(Scalar::ZERO).is_zero();
+                // Substitute an arbitrary non-zero scalar for an invalid one.
+                let scalar = scalar.unwrap_or(Scalar::ONE);
+                let scalar = Scalar::conditional_select(&Scalar::ONE, &scalar, is_some);
+                let scalar = NonZeroScalar::new(scalar).expect("the scalar is non-zero");
+                CtOption::new(Self::from(scalar), is_some)
+            }
+        }
+
+        impl CompactEncoding for PublicKey<$curve> {
+            type Bytes = [u8; $size + 1];
+
+            fn to_compact_bytes(&self) -> Self::Bytes {
+                self.as_affine().to_compact_bytes()
+            }
+
+            fn from_compact_bytes(bytes: &Self::Bytes) -> CtOption<Self> {
+                let point = AffinePoint::<$curve>::from_compact_bytes(bytes);
+                let is_some = point.is_some()
+                    & !point
+                        .unwrap_or(AffinePoint::<$curve>::IDENTITY)
+                        .is_identity();
+                let point = AffinePoint::<$curve>::conditional_select(
+                    &AffinePoint::<$curve>::GENERATOR,
+                    &point.unwrap_or(AffinePoint::<$curve>::GENERATOR),
+                    is_some,
+                );
+                let public_key = Self::from_affine(point).expect("the point is not the identity");
+                CtOption::new(public_key, is_some)
+            }
+        }
+
+        #[cfg(feature = "ecdsa")]
+        impl CompactEncoding for Signature<$curve> {
+            type Bytes = [u8; 2 * $size];
+
+            fn to_compact_bytes(&self) -> Self::Bytes {
+                let mut bytes = Self::Bytes::default();
+                bytes[..$size].copy_from_slice(&self.r().to_compact_bytes());
+                bytes[$size..].copy_from_slice(&self.s().to_compact_bytes());
+                bytes
+            }
+
+            fn from_compact_bytes(bytes: &Self::Bytes) -> CtOption<Self> {
+                type Scalar = <$curve as CurveArithmetic>::Scalar;
+                let r_bytes = bytes[..$size]
+                    .try_into()
+                    .expect("slice has the correct length");
+                let s_bytes = bytes[$size..]
+                    .try_into()
+                    .expect("slice has the correct length");
+                let r = Scalar::from_compact_bytes(&r_bytes);
+                let s = Scalar::from_compact_bytes(&s_bytes);
+                let is_some = r.is_some() & s.is_some();
+
+                // Signatures are public, so there is no need for constant-time decoding.
+                let r = r.unwrap_or(Scalar::ZERO);
+                let s = s.unwrap_or(Scalar::ZERO);
+                match Signature::from_scalars(r.to_repr(), s.to_repr()) {
+                    Ok(signature) => CtOption::new(signature, is_some),
+                    Err(_) => {
+                        let placeholder = Signature::from_scalars(Scalar::ONE, Scalar::ONE)
+                            .expect("the scalars are non-zero");
+                        CtOption::new(placeholder, Choice::from(0))
+                    }
+                }
+            }
+        }
+    };
+}
+
+impl_keys!(TinyCurve16, 2);
+impl_keys!(TinyCurve32, 4);
+impl_keys!(TinyCurve64, 8);
+
+#[cfg(test)]
+mod tests {
+    use primeorder::{
+        elliptic_curve::{group::Curve, CurveArithmetic, Field},
+        AffinePoint, PrimeCurveParams, ProjectivePoint,
+    };
+    use rand_core::OsRng;
+
+    use super::CompactEncoding;
+    use crate::{TinyCurve16, TinyCurve32, TinyCurve64};
+
+    fn check_encoding<C>(size: usize)
+    where
+        C: PrimeCurveParams,
+        C::Scalar: CompactEncoding,
+        C::FieldElement: CompactEncoding,
+        AffinePoint<C>: CompactEncoding,
+        ProjectivePoint<C>: CompactEncoding + Curve<AffineRepr = AffinePoint<C>>,
+    {
+        type Scalar<C> = <C as CurveArithmetic>::Scalar;
+
+        // Round trips
+        let scalar = Scalar::<C>::random(&mut OsRng);
+        let bytes = scalar.to_compact_bytes();
+        assert_eq!(bytes.as_ref().len(), size);
+        assert_eq!(Scalar::<C>::from_compact_bytes(&bytes).unwrap(), scalar);
+
+        let element = C::FieldElement::random(&mut OsRng);
+        let bytes = element.to_compact_bytes();
+        assert_eq!(bytes.as_ref().len(), size);
+        assert_eq!(
+            C::FieldElement::from_compact_bytes(&bytes).unwrap(),
+            element
+        );
+
+        let point = ProjectivePoint::<C>::GENERATOR * Scalar::<C>::random(&mut OsRng);
+        let bytes = point.to_compact_bytes();
+        assert_eq!(bytes.as_ref().len(), size + 1);
+        assert_eq!(
+            ProjectivePoint::<C>::from_compact_bytes(&bytes).unwrap(),
+            point
+        );
+        assert_eq!(
+            AffinePoint::<C>::from_compact_bytes(&point.to_affine().to_compact_bytes()).unwrap(),
+            point.to_affine()
+        );
+
+        let bytes = ProjectivePoint::<C>::IDENTITY.to_compact_bytes();
+        assert!(bytes.as_ref().iter().all(|byte| byte == &0));
+        assert_eq!(
+            ProjectivePoint::<C>::from_compact_bytes(&bytes).unwrap(),
+            ProjectivePoint::<C>::IDENTITY
+        );
+
+        // Values out of range (all the moduli are less than `2^bits - 1`)
+        let mut bytes = <Scalar<C> as CompactEncoding>::Bytes::default();
+        bytes.as_mut().fill(0xff);
+        assert!(bool::from(
+            Scalar::<C>::from_compact_bytes(&bytes).is_none()
+        ));
+
+        let mut bytes = <C::FieldElement as CompactEncoding>::Bytes::default();
+        bytes.as_mut().fill(0xff);
+        assert!(bool::from(
+            C::FieldElement::from_compact_bytes(&bytes).is_none()
+        ));
+
+        let mut bytes = point.to_compact_bytes();
+        bytes.as_mut()[1..].fill(0xff);
+        assert!(bool::from(
+            ProjectivePoint::<C>::from_compact_bytes(&bytes).is_none()
+        ));
+
+        // Invalid tags
+        let valid_bytes = point.to_compact_bytes();
+        for tag in [0, 1, 4, 0xff] {
+            let mut bytes = valid_bytes;
+            bytes.as_mut()[0] = tag;
+            assert!(bool::from(
+                ProjectivePoint::<C>::from_compact_bytes(&bytes).is_none()
+            ));
+        }
+
+        // An x-coordinate without a point on the curve
+        // (about a half of the field elements are such, so the search is short)
+        let x = (0u64..256)
+            .map(C::FieldElement::from)
+            .find(|x| {
+                let y2 = x.square() * x + C::EQUATION_A * x + C::EQUATION_B;
+                bool::from(y2.sqrt().is_none())
+            })
+            .expect("an x-coordinate without a point exists among the first 256 values");
+        for tag in [2, 3] {
+            let mut bytes = valid_bytes;
+            bytes.as_mut()[0] = tag;
+            bytes.as_mut()[1..].copy_from_slice(x.to_compact_bytes().as_ref());
+            assert!(bool::from(
+                ProjectivePoint::<C>::from_compact_bytes(&bytes).is_none()
+            ));
+        }
+    }
+
+    #[test]
+    fn encoding() {
+        check_encoding::<TinyCurve16>(2);
+        check_encoding::<TinyCurve32>(4);
+        check_encoding::<TinyCurve64>(8);
+    }
+}
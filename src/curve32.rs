@@ -8,11 +8,14 @@ use primeorder::{
 
 #[cfg(feature = "ecdsa")]
 use ::ecdsa::hazmat::{DigestPrimitive, VerifyPrimitive};
+#[cfg(feature = "ecdsa")]
+use sha2::Sha256;
 
 #[cfg(feature = "pkcs8")]
 use primeorder::elliptic_curve::pkcs8::{AssociatedOid, ObjectIdentifier};
 
 use crate::{
+    point::Point,
     prime_field::{FieldElement, ReprSizeTypenum, ReprUint},
     traits::{Modulus, PrimeFieldConstants},
 };
@@ -20,8 +23,9 @@ use crate::{
 #[cfg(feature = "ecdsa")]
 use crate::hash::TinyHash;
 
-const ORDER: u64 = 0xffff0f07;
-const FIELD_MODULUS: u64 = 0xffffff67;
+pub(crate) const ORDER: u64 = 0xffff0f07;
+pub(crate) const FIELD_MODULUS: u64 = 0xffffff67;
+pub(crate) const GENERATOR: (u64, u64) = (4274000713, 443355223);
 
 impl PrimeFieldConstants<u32> for Modulus<u32, FIELD_MODULUS> {
     type Repr = FieldBytes<TinyCurve32>;
@@ -51,6 +55,15 @@ impl PrimeFieldConstants<u32> for Modulus<u32, ORDER> {
     const DELTA: u32 = 9;
 }
 
+/// A scalar of [`TinyCurve32`] (an integer modulo the curve order).
+pub type Scalar32 = FieldElement<u32, ORDER>;
+
+/// An element of the base field of [`TinyCurve32`].
+pub type FieldElement32 = FieldElement<u32, FIELD_MODULUS>;
+
+/// A point of [`TinyCurve32`] with formatting and parsing.
+pub type Point32 = Point<TinyCurve32>;
+
 /// An elliptic curve with a 32-bit order.
 #[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord)]
 pub struct TinyCurve32;
@@ -64,7 +77,7 @@ impl Curve for TinyCurve32 {
 impl FieldBytesEncoding<TinyCurve32> for <TinyCurve32 as Curve>::Uint {}
 
 impl CurveArithmetic for TinyCurve32 {
-    type Scalar = FieldElement<u32, ORDER>;
+    type Scalar = Scalar32;
     type AffinePoint = AffinePoint<Self>;
     type ProjectivePoint = ProjectivePoint<Self>;
 }
@@ -72,14 +85,15 @@ impl CurveArithmetic for TinyCurve32 {
 impl PrimeCurve for TinyCurve32 {}
 
 impl PrimeCurveParams for TinyCurve32 {
-    type FieldElement = FieldElement<u32, FIELD_MODULUS>;
+    type FieldElement = FieldElement32;
     type PointArithmetic = EquationAIsMinusThree;
 
-    const EQUATION_A: Self::FieldElement = FieldElement::new_unchecked(FIELD_MODULUS as u32 - 3);
-    const EQUATION_B: Self::FieldElement = FieldElement::new_unchecked(8);
+    const EQUATION_A: Self::FieldElement =
+        Self::FieldElement::new_unchecked(FIELD_MODULUS as u32 - 3);
+    const EQUATION_B: Self::FieldElement = Self::FieldElement::new_unchecked(8);
     const GENERATOR: (Self::FieldElement, Self::FieldElement) = (
-        FieldElement::new_unchecked(4274000713),
-        FieldElement::new_unchecked(443355223),
+        Self::FieldElement::new_unchecked(GENERATOR.0 as u32),
+        Self::FieldElement::new_unchecked(GENERATOR.1 as u32),
     );
 }
 
@@ -92,7 +106,7 @@ impl VerifyPrimitive<TinyCurve32> for AffinePoint<TinyCurve32> {}
 
 #[cfg(feature = "ecdsa")]
 impl DigestPrimitive for TinyCurve32 {
-    type Digest = TinyHash<4>;
+    type Digest = TinyHash<Sha256, 4>;
 }
 
 #[cfg(feature = "pkcs8")]
@@ -104,9 +118,10 @@ impl AssociatedOid for TinyCurve32 {
 mod tests {
     use primeorder::{
         elliptic_curve::{
-            bigint::Encoding,
+            bigint::{Encoding, U256, U512},
+            ff::FromUniformBytes,
             generic_array::GenericArray,
-            ops::{MulByGenerator, Reduce},
+            ops::{MulByGenerator, Reduce, ReduceNonZero},
             CurveArithmetic, Field, FieldBytesSize, ProjectivePoint,
         },
         PrimeField,
@@ -150,6 +165,31 @@ mod tests {
         assert!(bool::from(Scalar::from_repr(repr).is_none()));
     }
 
+    #[test]
+    fn wide_reduction() {
+        let x = u128::MAX - 12345;
+        assert_eq!(
+            Scalar::reduce(U256::from_u128(x)),
+            Scalar::reduce(ReprUint::from_u128(x))
+        );
+        assert_eq!(
+            Scalar::reduce(U256::MAX),
+            Scalar::from(2u64).pow([256]) - Scalar::ONE
+        );
+        assert_eq!(
+            Scalar::reduce(U512::MAX),
+            Scalar::from(2u64).pow([512]) - Scalar::ONE
+        );
+        assert_eq!(
+            Scalar::from_uniform_bytes(&[0xff; 64]),
+            Scalar::reduce(U512::MAX)
+        );
+        assert_eq!(
+            <Scalar as ReduceNonZero<U512>>::reduce_nonzero(U512::ZERO),
+            Scalar::ONE
+        );
+    }
+
     prop_compose! {
         /// Generate a random odd modulus.
         fn scalar()(n in any::<u64>()) -> Scalar {
@@ -164,6 +204,13 @@ mod tests {
             let p2 = Point::mul_by_generator(&(x + y));
             assert_eq!(p1, p2);
         }
+
+        #[test]
+        fn invert_vartime(x in scalar()) {
+            let inv = Option::<Scalar>::from(Field::invert(&x));
+            let inv_vartime = Option::<Scalar>::from(x.invert_vartime());
+            assert_eq!(inv, inv_vartime);
+        }
     }
 }
 
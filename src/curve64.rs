@@ -8,11 +8,14 @@ use primeorder::{
 
 #[cfg(feature = "ecdsa")]
 use ::ecdsa::hazmat::{DigestPrimitive, VerifyPrimitive};
+#[cfg(feature = "ecdsa")]
+use sha2::Sha256;
 
 #[cfg(feature = "pkcs8")]
 use primeorder::elliptic_curve::pkcs8::{AssociatedOid, ObjectIdentifier};
 
 use crate::{
+    point::Point,
     prime_field::{FieldElement, ReprSizeTypenum, ReprUint},
     traits::{Modulus, PrimeFieldConstants},
 };
@@ -20,8 +23,9 @@ use crate::{
 #[cfg(feature = "ecdsa")]
 use crate::hash::TinyHash;
 
-const ORDER: u64 = 0xffffffff1a0a85df;
-const FIELD_MODULUS: u64 = 0xfffffffffffffc7f;
+pub(crate) const ORDER: u64 = 0xffffffff1a0a85df;
+pub(crate) const FIELD_MODULUS: u64 = 0xfffffffffffffc7f;
+pub(crate) const GENERATOR: (u64, u64) = (11619086278950426528, 2765382488766937725);
 
 impl PrimeFieldConstants<u64> for Modulus<u64, FIELD_MODULUS> {
     type Repr = FieldBytes<TinyCurve64>;
@@ -51,6 +55,15 @@ impl PrimeFieldConstants<u64> for Modulus<u64, ORDER> {
     const DELTA: u64 = 25;
 }
 
+/// A scalar of [`TinyCurve64`] (an integer modulo the curve order).
+pub type Scalar64 = FieldElement<u64, ORDER>;
+
+/// An element of the base field of [`TinyCurve64`].
+pub type FieldElement64 = FieldElement<u64, FIELD_MODULUS>;
+
+/// A point of [`TinyCurve64`] with formatting and parsing.
+pub type Point64 = Point<TinyCurve64>;
+
 /// An elliptic curve with a 64-bit order.
 #[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord)]
 pub struct TinyCurve64;
@@ -64,7 +77,7 @@ impl Curve for TinyCurve64 {
 impl FieldBytesEncoding<TinyCurve64> for <TinyCurve64 as Curve>::Uint {}
 
 impl CurveArithmetic for TinyCurve64 {
-    type Scalar = FieldElement<u64, ORDER>;
+    type Scalar = Scalar64;
     type AffinePoint = AffinePoint<Self>;
     type ProjectivePoint = ProjectivePoint<Self>;
 }
@@ -72,14 +85,14 @@ impl CurveArithmetic for TinyCurve64 {
 impl PrimeCurve for TinyCurve64 {}
 
 impl PrimeCurveParams for TinyCurve64 {
-    type FieldElement = FieldElement<u64, FIELD_MODULUS>;
+    type FieldElement = FieldElement64;
     type PointArithmetic = EquationAIsMinusThree;
 
-    const EQUATION_A: Self::FieldElement = FieldElement::new_unchecked(FIELD_MODULUS - 3);
-    const EQUATION_B: Self::FieldElement = FieldElement::new_unchecked(6);
+    const EQUATION_A: Self::FieldElement = Self::FieldElement::new_unchecked(FIELD_MODULUS - 3);
+    const EQUATION_B: Self::FieldElement = Self::FieldElement::new_unchecked(6);
     const GENERATOR: (Self::FieldElement, Self::FieldElement) = (
-        FieldElement::new_unchecked(11619086278950426528),
-        FieldElement::new_unchecked(2765382488766937725),
+        Self::FieldElement::new_unchecked(GENERATOR.0),
+        Self::FieldElement::new_unchecked(GENERATOR.1),
     );
 }
 
@@ -92,7 +105,7 @@ impl VerifyPrimitive<TinyCurve64> for AffinePoint<TinyCurve64> {}
 
 #[cfg(feature = "ecdsa")]
 impl DigestPrimitive for TinyCurve64 {
-    type Digest = TinyHash<8>;
+    type Digest = TinyHash<Sha256, 8>;
 }
 
 #[cfg(feature = "pkcs8")]
@@ -104,9 +117,10 @@ impl AssociatedOid for TinyCurve64 {
 mod tests {
     use primeorder::{
         elliptic_curve::{
-            bigint::Encoding,
+            bigint::{Encoding, U256, U512},
+            ff::FromUniformBytes,
             generic_array::GenericArray,
-            ops::{MulByGenerator, Reduce},
+            ops::{MulByGenerator, Reduce, ReduceNonZero},
             CurveArithmetic, Field, FieldBytesSize, ProjectivePoint,
         },
         PrimeField,
@@ -150,6 +164,31 @@ mod tests {
         assert!(bool::from(Scalar::from_repr(repr).is_none()));
     }
 
+    #[test]
+    fn wide_reduction() {
+        let x = u128::MAX - 12345;
+        assert_eq!(
+            Scalar::reduce(U256::from_u128(x)),
+            Scalar::reduce(ReprUint::from_u128(x))
+        );
+        assert_eq!(
+            Scalar::reduce(U256::MAX),
+            Scalar::from(2u64).pow([256]) - Scalar::ONE
+        );
+        assert_eq!(
+            Scalar::reduce(U512::MAX),
+            Scalar::from(2u64).pow([512]) - Scalar::ONE
+        );
+        assert_eq!(
+            Scalar::from_uniform_bytes(&[0xff; 64]),
+            Scalar::reduce(U512::MAX)
+        );
+        assert_eq!(
+            <Scalar as ReduceNonZero<U512>>::reduce_nonzero(U512::ZERO),
+            Scalar::ONE
+        );
+    }
+
     prop_compose! {
         /// Generate a random odd modulus.
         fn scalar()(n in any::<u64>()) -> Scalar {
@@ -164,6 +203,13 @@ mod tests {
             let p2 = Point::mul_by_generator(&(x + y));
             assert_eq!(p1, p2);
         }
+
+        #[test]
+        fn invert_vartime(x in scalar()) {
+            let inv = Option::<Scalar>::from(Field::invert(&x));
+            let inv_vartime = Option::<Scalar>::from(x.invert_vartime());
+            assert_eq!(inv, inv_vartime);
+        }
     }
 }
 
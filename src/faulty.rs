@@ -0,0 +1,972 @@
+//! A copy of [`TinyCurve16`] with arithmetic defects that can be switched on.
+//!
+//! The intended use is mutation testing: a protocol test suite instantiated with
+//! a [`FaultyCurve16`] with some defects enabled is expected to fail.
+//!
+//! `primeorder` does not allow customizing its point arithmetic,
+//! so the types here are thin wrappers over the ones of [`TinyCurve16`]
+//! that inject the defects on the way.
+
+use core::{
+    iter::{Product, Sum},
+    ops::{Add, AddAssign, Mul, MulAssign, Neg, ShrAssign, Sub, SubAssign},
+};
+
+use primeorder::{
+    elliptic_curve::{
+        generic_array::{
+            typenum::{self, Unsigned},
+            GenericArray,
+        },
+        group::{self, Group},
+        ops::{Invert, LinearCombination, MulByGenerator, Reduce, ReduceNonZero},
+        point::{AffineCoordinates, DecompressPoint, PointCompression},
+        rand_core::RngCore,
+        scalar::{FromUintUnchecked, IsHigh},
+        sec1::{self, EncodedPoint, FromEncodedPoint, ToEncodedPoint},
+        subtle::{Choice, ConditionallySelectable, ConstantTimeEq, CtOption},
+        zeroize::DefaultIsZeroes,
+        AffinePoint, Curve, CurveArithmetic, Field, FieldBytes, FieldBytesEncoding, PrimeCurve,
+        PrimeField, ProjectivePoint, ScalarPrimitive,
+    },
+    PrimeCurveParams,
+};
+
+#[cfg(feature = "ecdsa")]
+use ::{
+    ecdsa::{
+        hazmat::{DigestPrimitive, SignPrimitive, VerifyPrimitive},
+        SignatureSize,
+    },
+    primeorder::elliptic_curve::generic_array::ArrayLength,
+    sha2::Sha256,
+};
+
+use crate::{
+    curve16::{GENERATOR, ORDER},
+    prime_field::{ReprSizeTypenum, ReprUint},
+    FieldElement16, TinyCurve16,
+};
+
+#[cfg(feature = "ecdsa")]
+use crate::hash::TinyHash;
+
+type Scalar = <TinyCurve16 as CurveArithmetic>::Scalar;
+
+/// Defects that can be switched on in [`FaultyCurve16`].
+///
+/// The constants are bit flags and can be combined with `|`,
+/// e.g. `FaultyCurve16<{ Faults::DOUBLING | Faults::SQRT }>`.
+#[derive(Debug, Clone, Copy)]
+pub struct Faults;
+
+impl Faults {
+    /// No defects: the curve behaves like [`TinyCurve16`].
+    pub const NONE: u32 = 0;
+
+    /// Point doubling returns `2P + G` instead of `2P` for the points
+    /// whose x-coordinate equals `DOUBLING_X` (the second parameter of [`FaultyCurve16`]).
+    /// By default it is the x-coordinate of the generator `G`, so `G` and `-G` are affected.
+    pub const DOUBLING: u32 = 1 << 0;
+
+    /// Point addition does not handle `P + (-P)` and returns `2P` instead of the identity.
+    pub const ADDITION_OF_NEGATION: u32 = 1 << 1;
+
+    /// Scalar reduction forgets to subtract the last multiple of the modulus:
+    /// [`Reduce`] maps integers in the range `[n, 2n)` to `x - n + 1` instead of `x - n`,
+    /// and [`ReduceNonZero`] maps integers in the range `[n - 1, 2n - 2)`
+    /// to `x - n + 3` instead of `x - n + 2`.
+    pub const SCALAR_REDUCTION: u32 = 1 << 2;
+
+    /// Square roots return the other one of the two roots.
+    ///
+    /// In the base field, point decompression relies on the square root being even,
+    /// so decompressing a point `P` results in `-P`.
+    /// For scalars, [`Field::sqrt`] and [`Field::sqrt_ratio`] are affected.
+    pub const SQRT: u32 = 1 << 3;
+
+    /// All of the above.
+    pub const ALL: u32 =
+        Self::DOUBLING | Self::ADDITION_OF_NEGATION | Self::SCALAR_REDUCTION | Self::SQRT;
+}
+
+const fn is_enabled(faults: u32, fault: u32) -> bool {
+    faults & fault != 0
+}
+
+/// Returns the even square root of `x` in the base field
+/// (or the odd one if [`Faults::SQRT`] is enabled).
+fn field_sqrt<const FAULTS: u32>(x: &FieldElement16) -> CtOption<FieldElement16> {
+    Field::sqrt(x).map(|root| {
+        let odd_wanted = Choice::from(is_enabled(FAULTS, Faults::SQRT) as u8);
+        FieldElement16::conditional_select(&-root, &root, root.is_odd().ct_eq(&odd_wanted))
+    })
+}
+
+/// Returns `root` or, if [`Faults::SQRT`] is enabled, the other square root.
+fn scalar_root<const FAULTS: u32>(root: Scalar) -> Scalar {
+    if is_enabled(FAULTS, Faults::SQRT) {
+        -root
+    } else {
+        root
+    }
+}
+
+/// An elliptic curve with the same parameters as [`TinyCurve16`],
+/// but with the defects from [`Faults`] enabled in `FAULTS`.
+///
+/// `DOUBLING_X` is the x-coordinate of the points affected by [`Faults::DOUBLING`].
+///
+/// **Warning:** this curve is broken on purpose.
+/// Only use it to check that your tests catch the defects.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord)]
+pub struct FaultyCurve16<const FAULTS: u32, const DOUBLING_X: u64 = { GENERATOR.0 }>;
+
+impl<const FAULTS: u32, const DOUBLING_X: u64> Curve for FaultyCurve16<FAULTS, DOUBLING_X> {
+    type FieldBytesSize = ReprSizeTypenum;
+    type Uint = ReprUint;
+    const ORDER: Self::Uint = Self::Uint::from_u64(ORDER);
+}
+
+impl<const FAULTS: u32, const DOUBLING_X: u64> FieldBytesEncoding<FaultyCurve16<FAULTS, DOUBLING_X>>
+    for <FaultyCurve16<FAULTS, DOUBLING_X> as Curve>::Uint
+{
+}
+
+impl<const FAULTS: u32, const DOUBLING_X: u64> CurveArithmetic
+    for FaultyCurve16<FAULTS, DOUBLING_X>
+{
+    type Scalar = FaultyScalar<FAULTS, DOUBLING_X>;
+    type AffinePoint = FaultyAffinePoint<FAULTS, DOUBLING_X>;
+    type ProjectivePoint = FaultyProjectivePoint<FAULTS, DOUBLING_X>;
+}
+
+impl<const FAULTS: u32, const DOUBLING_X: u64> PrimeCurve for FaultyCurve16<FAULTS, DOUBLING_X> {}
+
+impl<const FAULTS: u32, const DOUBLING_X: u64> PointCompression
+    for FaultyCurve16<FAULTS, DOUBLING_X>
+{
+    const COMPRESS_POINTS: bool = true;
+}
+
+#[cfg(feature = "ecdsa")]
+impl<const FAULTS: u32, const DOUBLING_X: u64> VerifyPrimitive<FaultyCurve16<FAULTS, DOUBLING_X>>
+    for FaultyAffinePoint<FAULTS, DOUBLING_X>
+{
+}
+
+#[cfg(feature = "ecdsa")]
+impl<const FAULTS: u32, const DOUBLING_X: u64> DigestPrimitive
+    for FaultyCurve16<FAULTS, DOUBLING_X>
+{
+    type Digest = TinyHash<Sha256, 2>;
+}
+
+/// A scalar of [`FaultyCurve16`].
+#[derive(Default, Debug, Clone, Copy, PartialOrd, Ord, PartialEq, Eq)]
+pub struct FaultyScalar<const FAULTS: u32, const DOUBLING_X: u64 = { GENERATOR.0 }>(Scalar);
+
+impl<const FAULTS: u32, const DOUBLING_X: u64> DefaultIsZeroes
+    for FaultyScalar<FAULTS, DOUBLING_X>
+{
+}
+
+impl<const FAULTS: u32, const DOUBLING_X: u64> From<u64> for FaultyScalar<FAULTS, DOUBLING_X> {
+    fn from(source: u64) -> Self {
+        Self(source.into())
+    }
+}
+
+impl<const FAULTS: u32, const DOUBLING_X: u64>
+    From<ScalarPrimitive<FaultyCurve16<FAULTS, DOUBLING_X>>> for FaultyScalar<FAULTS, DOUBLING_X>
+{
+    fn from(source: ScalarPrimitive<FaultyCurve16<FAULTS, DOUBLING_X>>) -> Self {
+        Self(source.into())
+    }
+}
+
+impl<const FAULTS: u32, const DOUBLING_X: u64> FromUintUnchecked
+    for FaultyScalar<FAULTS, DOUBLING_X>
+{
+    type Uint = ReprUint;
+
+    fn from_uint_unchecked(uint: Self::Uint) -> Self {
+        Self(Scalar::from_uint_unchecked(uint))
+    }
+}
+
+impl<const FAULTS: u32, const DOUBLING_X: u64> From<FaultyScalar<FAULTS, DOUBLING_X>>
+    for GenericArray<u8, typenum::U24>
+{
+    fn from(source: FaultyScalar<FAULTS, DOUBLING_X>) -> Self {
+        source.0.into()
+    }
+}
+
+impl<const FAULTS: u32, const DOUBLING_X: u64> From<FaultyScalar<FAULTS, DOUBLING_X>>
+    for ScalarPrimitive<FaultyCurve16<FAULTS, DOUBLING_X>>
+{
+    fn from(source: FaultyScalar<FAULTS, DOUBLING_X>) -> Self {
+        source.0.into()
+    }
+}
+
+impl<const FAULTS: u32, const DOUBLING_X: u64> From<FaultyScalar<FAULTS, DOUBLING_X>> for ReprUint {
+    fn from(source: FaultyScalar<FAULTS, DOUBLING_X>) -> Self {
+        source.0.into()
+    }
+}
+
+impl<const FAULTS: u32, const DOUBLING_X: u64> Invert for FaultyScalar<FAULTS, DOUBLING_X> {
+    type Output = CtOption<Self>;
+
+    fn invert(&self) -> Self::Output {
+        Invert::invert(&self.0).map(Self)
+    }
+
+    fn invert_vartime(&self) -> Self::Output {
+        Invert::invert_vartime(&self.0).map(Self)
+    }
+}
+
+impl<const FAULTS: u32, const DOUBLING_X: u64> IsHigh for FaultyScalar<FAULTS, DOUBLING_X> {
+    fn is_high(&self) -> Choice {
+        self.0.is_high()
+    }
+}
+
+impl<const FAULTS: u32, const DOUBLING_X: u64> Reduce<ReprUint>
+    for FaultyScalar<FAULTS, DOUBLING_X>
+{
+    type Bytes = GenericArray<u8, ReprSizeTypenum>;
+
+    fn reduce(n: ReprUint) -> Self {
+        let reduced = Scalar::reduce(n);
+        let order = ReprUint::from_u64(ORDER);
+        if is_enabled(FAULTS, Faults::SCALAR_REDUCTION)
+            && n >= order
+            && n < order.wrapping_add(&order)
+        {
+            Self(reduced + Scalar::ONE)
+        } else {
+            Self(reduced)
+        }
+    }
+
+    fn reduce_bytes(bytes: &Self::Bytes) -> Self {
+        let uint = ReprUint::from_be_slice(bytes);
+        Self::reduce(uint)
+    }
+}
+
+impl<const FAULTS: u32, const DOUBLING_X: u64> ReduceNonZero<ReprUint>
+    for FaultyScalar<FAULTS, DOUBLING_X>
+{
+    fn reduce_nonzero(n: ReprUint) -> Self {
+        let reduced = Scalar::reduce_nonzero(n);
+        let modulus = ReprUint::from_u64(ORDER - 1);
+        if is_enabled(FAULTS, Faults::SCALAR_REDUCTION)
+            && n >= modulus
+            && n < modulus.wrapping_add(&modulus)
+        {
+            Self(reduced + Scalar::ONE)
+        } else {
+            Self(reduced)
+        }
+    }
+
+    fn reduce_nonzero_bytes(bytes: &Self::Bytes) -> Self {
+        let uint = ReprUint::from_be_slice(bytes);
+        Self::reduce_nonzero(uint)
+    }
+}
+
+impl<const FAULTS: u32, const DOUBLING_X: u64> ShrAssign<usize>
+    for FaultyScalar<FAULTS, DOUBLING_X>
+{
+    fn shr_assign(&mut self, shift: usize) {
+        self.0.shr_assign(shift)
+    }
+}
+
+/// Implements a binary operation `Self x Rhs -> Self` for owned and borrowed `Rhs`,
+/// given the assigning operation for borrowed `Rhs`.
+macro_rules! impl_binary_op {
+    ($lhs:ident, $rhs:ty, $trait:ident, $method:ident, $assign_trait:ident, $assign_method:ident) => {
+        impl<const FAULTS: u32, const DOUBLING_X: u64> $assign_trait<$rhs>
+            for $lhs<FAULTS, DOUBLING_X>
+        {
+            fn $assign_method(&mut self, rhs: $rhs) {
+                self.$assign_method(&rhs)
+            }
+        }
+
+        impl<const FAULTS: u32, const DOUBLING_X: u64> $trait<$rhs> for $lhs<FAULTS, DOUBLING_X> {
+            type Output = Self;
+            fn $method(mut self, rhs: $rhs) -> Self::Output {
+                self.$assign_method(&rhs);
+                self
+            }
+        }
+
+        impl<'a, const FAULTS: u32, const DOUBLING_X: u64> $trait<&'a $rhs>
+            for $lhs<FAULTS, DOUBLING_X>
+        {
+            type Output = Self;
+            fn $method(mut self, rhs: &'a $rhs) -> Self::Output {
+                self.$assign_method(rhs);
+                self
+            }
+        }
+    };
+}
+
+impl<'a, const FAULTS: u32, const DOUBLING_X: u64> AddAssign<&'a Self>
+    for FaultyScalar<FAULTS, DOUBLING_X>
+{
+    fn add_assign(&mut self, rhs: &'a Self) {
+        self.0 += rhs.0
+    }
+}
+
+impl<'a, const FAULTS: u32, const DOUBLING_X: u64> SubAssign<&'a Self>
+    for FaultyScalar<FAULTS, DOUBLING_X>
+{
+    fn sub_assign(&mut self, rhs: &'a Self) {
+        self.0 -= rhs.0
+    }
+}
+
+impl<'a, const FAULTS: u32, const DOUBLING_X: u64> MulAssign<&'a Self>
+    for FaultyScalar<FAULTS, DOUBLING_X>
+{
+    fn mul_assign(&mut self, rhs: &'a Self) {
+        self.0 *= rhs.0
+    }
+}
+
+impl_binary_op!(
+    FaultyScalar,
+    FaultyScalar<FAULTS, DOUBLING_X>,
+    Add,
+    add,
+    AddAssign,
+    add_assign
+);
+impl_binary_op!(
+    FaultyScalar,
+    FaultyScalar<FAULTS, DOUBLING_X>,
+    Sub,
+    sub,
+    SubAssign,
+    sub_assign
+);
+impl_binary_op!(
+    FaultyScalar,
+    FaultyScalar<FAULTS, DOUBLING_X>,
+    Mul,
+    mul,
+    MulAssign,
+    mul_assign
+);
+
+impl<const FAULTS: u32, const DOUBLING_X: u64> Sum for FaultyScalar<FAULTS, DOUBLING_X> {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::ZERO, Add::add)
+    }
+}
+
+impl<'a, const FAULTS: u32, const DOUBLING_X: u64> Sum<&'a Self>
+    for FaultyScalar<FAULTS, DOUBLING_X>
+{
+    fn sum<I: Iterator<Item = &'a Self>>(iter: I) -> Self {
+        iter.fold(Self::ZERO, Add::add)
+    }
+}
+
+impl<const FAULTS: u32, const DOUBLING_X: u64> Product for FaultyScalar<FAULTS, DOUBLING_X> {
+    fn product<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::ONE, Mul::mul)
+    }
+}
+
+impl<'a, const FAULTS: u32, const DOUBLING_X: u64> Product<&'a Self>
+    for FaultyScalar<FAULTS, DOUBLING_X>
+{
+    fn product<I: Iterator<Item = &'a Self>>(iter: I) -> Self {
+        iter.fold(Self::ONE, Mul::mul)
+    }
+}
+
+impl<const FAULTS: u32, const DOUBLING_X: u64> Neg for FaultyScalar<FAULTS, DOUBLING_X> {
+    type Output = Self;
+    fn neg(self) -> Self::Output {
+        Self(-self.0)
+    }
+}
+
+impl<const FAULTS: u32, const DOUBLING_X: u64> ConstantTimeEq for FaultyScalar<FAULTS, DOUBLING_X> {
+    fn ct_eq(&self, rhs: &Self) -> Choice {
+        self.0.ct_eq(&rhs.0)
+    }
+}
+
+impl<const FAULTS: u32, const DOUBLING_X: u64> ConditionallySelectable
+    for FaultyScalar<FAULTS, DOUBLING_X>
+{
+    fn conditional_select(lhs: &Self, rhs: &Self, choice: Choice) -> Self {
+        Self(Scalar::conditional_select(&lhs.0, &rhs.0, choice))
+    }
+}
+
+impl<const FAULTS: u32, const DOUBLING_X: u64> Field for FaultyScalar<FAULTS, DOUBLING_X> {
+    const ZERO: Self = Self(Scalar::ZERO);
+    const ONE: Self = Self(Scalar::ONE);
+
+    fn random(rng: impl RngCore) -> Self {
+        Self(Scalar::random(rng))
+    }
+
+    fn square(&self) -> Self {
+        Self(self.0.square())
+    }
+
+    fn double(&self) -> Self {
+        Self(self.0.double())
+    }
+
+    fn invert(&self) -> CtOption<Self> {
+        Field::invert(&self.0).map(Self)
+    }
+
+    fn sqrt(&self) -> CtOption<Self> {
+        self.0.sqrt().map(|root| Self(scalar_root::<FAULTS>(root)))
+    }
+
+    fn sqrt_ratio(num: &Self, div: &Self) -> (Choice, Self) {
+        let (is_square, res) = Scalar::sqrt_ratio(&num.0, &div.0);
+        (is_square, Self(scalar_root::<FAULTS>(res)))
+    }
+}
+
+impl<const FAULTS: u32, const DOUBLING_X: u64> PrimeField for FaultyScalar<FAULTS, DOUBLING_X> {
+    type Repr = FieldBytes<FaultyCurve16<FAULTS, DOUBLING_X>>;
+
+    const MODULUS: &'static str = <Scalar as PrimeField>::MODULUS;
+    const NUM_BITS: u32 = Scalar::NUM_BITS;
+    const CAPACITY: u32 = Scalar::CAPACITY;
+    const TWO_INV: Self = Self(Scalar::TWO_INV);
+    const MULTIPLICATIVE_GENERATOR: Self = Self(Scalar::MULTIPLICATIVE_GENERATOR);
+    const S: u32 = Scalar::S;
+    const ROOT_OF_UNITY: Self = Self(Scalar::ROOT_OF_UNITY);
+    const ROOT_OF_UNITY_INV: Self = Self(Scalar::ROOT_OF_UNITY_INV);
+    const DELTA: Self = Self(Scalar::DELTA);
+
+    fn from_repr(repr: Self::Repr) -> CtOption<Self> {
+        Scalar::from_repr(repr).map(Self)
+    }
+
+    fn to_repr(&self) -> Self::Repr {
+        self.0.to_repr()
+    }
+
+    fn is_odd(&self) -> Choice {
+        self.0.is_odd()
+    }
+}
+
+impl<const FAULTS: u32, const DOUBLING_X: u64> AsRef<Self> for FaultyScalar<FAULTS, DOUBLING_X> {
+    fn as_ref(&self) -> &Self {
+        self
+    }
+}
+
+#[cfg(feature = "ecdsa")]
+impl<const FAULTS: u32, const DOUBLING_X: u64> SignPrimitive<FaultyCurve16<FAULTS, DOUBLING_X>>
+    for FaultyScalar<FAULTS, DOUBLING_X>
+where
+    SignatureSize<FaultyCurve16<FAULTS, DOUBLING_X>>: ArrayLength<u8>,
+{
+}
+
+/// A point of [`FaultyCurve16`] in affine coordinates.
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FaultyAffinePoint<const FAULTS: u32, const DOUBLING_X: u64 = { GENERATOR.0 }>(
+    AffinePoint<TinyCurve16>,
+);
+
+impl<const FAULTS: u32, const DOUBLING_X: u64> AffineCoordinates
+    for FaultyAffinePoint<FAULTS, DOUBLING_X>
+{
+    type FieldRepr = FieldBytes<FaultyCurve16<FAULTS, DOUBLING_X>>;
+
+    fn x(&self) -> Self::FieldRepr {
+        self.0.x()
+    }
+
+    fn y_is_odd(&self) -> Choice {
+        self.0.y_is_odd()
+    }
+}
+
+impl<const FAULTS: u32, const DOUBLING_X: u64> ConditionallySelectable
+    for FaultyAffinePoint<FAULTS, DOUBLING_X>
+{
+    fn conditional_select(lhs: &Self, rhs: &Self, choice: Choice) -> Self {
+        Self(AffinePoint::<TinyCurve16>::conditional_select(
+            &lhs.0, &rhs.0, choice,
+        ))
+    }
+}
+
+impl<const FAULTS: u32, const DOUBLING_X: u64> ConstantTimeEq
+    for FaultyAffinePoint<FAULTS, DOUBLING_X>
+{
+    fn ct_eq(&self, rhs: &Self) -> Choice {
+        self.0.ct_eq(&rhs.0)
+    }
+}
+
+impl<const FAULTS: u32, const DOUBLING_X: u64> DefaultIsZeroes
+    for FaultyAffinePoint<FAULTS, DOUBLING_X>
+{
+}
+
+impl<const FAULTS: u32, const DOUBLING_X: u64> DecompressPoint<FaultyCurve16<FAULTS, DOUBLING_X>>
+    for FaultyAffinePoint<FAULTS, DOUBLING_X>
+{
+    fn decompress(
+        x: &FieldBytes<FaultyCurve16<FAULTS, DOUBLING_X>>,
+        y_is_odd: Choice,
+    ) -> CtOption<Self> {
+        let a = <TinyCurve16 as PrimeCurveParams>::EQUATION_A;
+        let b = <TinyCurve16 as PrimeCurveParams>::EQUATION_B;
+        FieldElement16::from_repr(*x).and_then(|x| {
+            let alpha = x * x * x + a * x + b;
+            field_sqrt::<FAULTS>(&alpha).and_then(|even_root| {
+                // Relies on `field_sqrt()` returning the even root.
+                let y = FieldElement16::conditional_select(&even_root, &-even_root, y_is_odd);
+                let point = EncodedPoint::<TinyCurve16>::from_affine_coordinates(
+                    &x.to_repr(),
+                    &y.to_repr(),
+                    false,
+                );
+                AffinePoint::<TinyCurve16>::from_encoded_point(&point).map(Self)
+            })
+        })
+    }
+}
+
+impl<const FAULTS: u32, const DOUBLING_X: u64> FromEncodedPoint<FaultyCurve16<FAULTS, DOUBLING_X>>
+    for FaultyAffinePoint<FAULTS, DOUBLING_X>
+{
+    fn from_encoded_point(
+        point: &EncodedPoint<FaultyCurve16<FAULTS, DOUBLING_X>>,
+    ) -> CtOption<Self> {
+        match point.coordinates() {
+            sec1::Coordinates::Compressed { x, y_is_odd } => {
+                Self::decompress(x, Choice::from(y_is_odd as u8))
+            }
+            _ => {
+                let point = EncodedPoint::<TinyCurve16>::from_bytes(point.as_bytes())
+                    .expect("the encodings of `TinyCurve16` and `FaultyCurve16` are the same");
+                AffinePoint::<TinyCurve16>::from_encoded_point(&point).map(Self)
+            }
+        }
+    }
+}
+
+impl<const FAULTS: u32, const DOUBLING_X: u64> ToEncodedPoint<FaultyCurve16<FAULTS, DOUBLING_X>>
+    for FaultyAffinePoint<FAULTS, DOUBLING_X>
+{
+    fn to_encoded_point(&self, compress: bool) -> EncodedPoint<FaultyCurve16<FAULTS, DOUBLING_X>> {
+        EncodedPoint::<FaultyCurve16<FAULTS, DOUBLING_X>>::from_bytes(
+            self.0.to_encoded_point(compress).as_bytes(),
+        )
+        .expect("the encodings of `TinyCurve16` and `FaultyCurve16` are the same")
+    }
+}
+
+impl<const FAULTS: u32, const DOUBLING_X: u64> From<FaultyProjectivePoint<FAULTS, DOUBLING_X>>
+    for FaultyAffinePoint<FAULTS, DOUBLING_X>
+{
+    fn from(source: FaultyProjectivePoint<FAULTS, DOUBLING_X>) -> Self {
+        Self(source.0.into())
+    }
+}
+
+/// A point of [`FaultyCurve16`] in projective coordinates.
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FaultyProjectivePoint<const FAULTS: u32, const DOUBLING_X: u64 = { GENERATOR.0 }>(
+    ProjectivePoint<TinyCurve16>,
+);
+
+impl<const FAULTS: u32, const DOUBLING_X: u64> From<FaultyAffinePoint<FAULTS, DOUBLING_X>>
+    for FaultyProjectivePoint<FAULTS, DOUBLING_X>
+{
+    fn from(source: FaultyAffinePoint<FAULTS, DOUBLING_X>) -> Self {
+        Self(source.0.into())
+    }
+}
+
+impl<const FAULTS: u32, const DOUBLING_X: u64> ConditionallySelectable
+    for FaultyProjectivePoint<FAULTS, DOUBLING_X>
+{
+    fn conditional_select(lhs: &Self, rhs: &Self, choice: Choice) -> Self {
+        Self(ProjectivePoint::<TinyCurve16>::conditional_select(
+            &lhs.0, &rhs.0, choice,
+        ))
+    }
+}
+
+impl<const FAULTS: u32, const DOUBLING_X: u64> ConstantTimeEq
+    for FaultyProjectivePoint<FAULTS, DOUBLING_X>
+{
+    fn ct_eq(&self, rhs: &Self) -> Choice {
+        self.0.ct_eq(&rhs.0)
+    }
+}
+
+impl<const FAULTS: u32, const DOUBLING_X: u64> DefaultIsZeroes
+    for FaultyProjectivePoint<FAULTS, DOUBLING_X>
+{
+}
+
+impl<'a, const FAULTS: u32, const DOUBLING_X: u64> AddAssign<&'a Self>
+    for FaultyProjectivePoint<FAULTS, DOUBLING_X>
+{
+    fn add_assign(&mut self, rhs: &'a Self) {
+        let sum = self.0 + rhs.0;
+        let is_negation = !self.0.is_identity() & sum.is_identity();
+        *self = if is_enabled(FAULTS, Faults::ADDITION_OF_NEGATION) && bool::from(is_negation) {
+            Self(self.0.double())
+        } else {
+            Self(sum)
+        };
+    }
+}
+
+impl<'a, const FAULTS: u32, const DOUBLING_X: u64> SubAssign<&'a Self>
+    for FaultyProjectivePoint<FAULTS, DOUBLING_X>
+{
+    fn sub_assign(&mut self, rhs: &'a Self) {
+        *self += -*rhs
+    }
+}
+
+impl<'a, const FAULTS: u32, const DOUBLING_X: u64>
+    AddAssign<&'a FaultyAffinePoint<FAULTS, DOUBLING_X>>
+    for FaultyProjectivePoint<FAULTS, DOUBLING_X>
+{
+    fn add_assign(&mut self, rhs: &'a FaultyAffinePoint<FAULTS, DOUBLING_X>) {
+        *self += Self::from(*rhs)
+    }
+}
+
+impl<'a, const FAULTS: u32, const DOUBLING_X: u64>
+    SubAssign<&'a FaultyAffinePoint<FAULTS, DOUBLING_X>>
+    for FaultyProjectivePoint<FAULTS, DOUBLING_X>
+{
+    fn sub_assign(&mut self, rhs: &'a FaultyAffinePoint<FAULTS, DOUBLING_X>) {
+        *self -= Self::from(*rhs)
+    }
+}
+
+impl<'a, const FAULTS: u32, const DOUBLING_X: u64> MulAssign<&'a FaultyScalar<FAULTS, DOUBLING_X>>
+    for FaultyProjectivePoint<FAULTS, DOUBLING_X>
+{
+    fn mul_assign(&mut self, rhs: &'a FaultyScalar<FAULTS, DOUBLING_X>) {
+        // A simple double-and-add, so that the defects of the group operations
+        // propagate into the scalar multiplication.
+        let scalar = u64::from_be_bytes(
+            rhs.to_repr()[ReprSizeTypenum::USIZE - 8..]
+                .try_into()
+                .expect("slice has the correct length"),
+        );
+        let mut result = Self::IDENTITY;
+        for i in (0..FaultyScalar::<FAULTS, DOUBLING_X>::NUM_BITS).rev() {
+            result = result.double();
+            let sum = result + *self;
+            result.conditional_assign(&sum, Choice::from(((scalar >> i) & 1) as u8));
+        }
+        *self = result;
+    }
+}
+
+impl_binary_op!(
+    FaultyProjectivePoint,
+    FaultyProjectivePoint<FAULTS, DOUBLING_X>,
+    Add,
+    add,
+    AddAssign,
+    add_assign
+);
+impl_binary_op!(
+    FaultyProjectivePoint,
+    FaultyProjectivePoint<FAULTS, DOUBLING_X>,
+    Sub,
+    sub,
+    SubAssign,
+    sub_assign
+);
+impl_binary_op!(
+    FaultyProjectivePoint,
+    FaultyAffinePoint<FAULTS, DOUBLING_X>,
+    Add,
+    add,
+    AddAssign,
+    add_assign
+);
+impl_binary_op!(
+    FaultyProjectivePoint,
+    FaultyAffinePoint<FAULTS, DOUBLING_X>,
+    Sub,
+    sub,
+    SubAssign,
+    sub_assign
+);
+impl_binary_op!(
+    FaultyProjectivePoint,
+    FaultyScalar<FAULTS, DOUBLING_X>,
+    Mul,
+    mul,
+    MulAssign,
+    mul_assign
+);
+
+impl<const FAULTS: u32, const DOUBLING_X: u64> Neg for FaultyProjectivePoint<FAULTS, DOUBLING_X> {
+    type Output = Self;
+    fn neg(self) -> Self::Output {
+        Self(-self.0)
+    }
+}
+
+impl<const FAULTS: u32, const DOUBLING_X: u64> Sum for FaultyProjectivePoint<FAULTS, DOUBLING_X> {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::IDENTITY, Add::add)
+    }
+}
+
+impl<'a, const FAULTS: u32, const DOUBLING_X: u64> Sum<&'a Self>
+    for FaultyProjectivePoint<FAULTS, DOUBLING_X>
+{
+    fn sum<I: Iterator<Item = &'a Self>>(iter: I) -> Self {
+        iter.fold(Self::IDENTITY, Add::add)
+    }
+}
+
+impl<const FAULTS: u32, const DOUBLING_X: u64> FaultyProjectivePoint<FAULTS, DOUBLING_X> {
+    const IDENTITY: Self = Self(ProjectivePoint::<TinyCurve16>::IDENTITY);
+    const GENERATOR: Self = Self(ProjectivePoint::<TinyCurve16>::GENERATOR);
+}
+
+impl<const FAULTS: u32, const DOUBLING_X: u64> Group for FaultyProjectivePoint<FAULTS, DOUBLING_X> {
+    type Scalar = FaultyScalar<FAULTS, DOUBLING_X>;
+
+    fn random(rng: impl RngCore) -> Self {
+        Self(ProjectivePoint::<TinyCurve16>::random(rng))
+    }
+
+    fn identity() -> Self {
+        Self::IDENTITY
+    }
+
+    fn generator() -> Self {
+        Self::GENERATOR
+    }
+
+    fn is_identity(&self) -> Choice {
+        self.0.is_identity()
+    }
+
+    fn double(&self) -> Self {
+        let doubled = self.0.double();
+        if is_enabled(FAULTS, Faults::DOUBLING)
+            && self.0.to_affine().x() == FieldElement16::from_u64_reduced(DOUBLING_X).to_repr()
+        {
+            Self(doubled + ProjectivePoint::<TinyCurve16>::GENERATOR)
+        } else {
+            Self(doubled)
+        }
+    }
+}
+
+impl<const FAULTS: u32, const DOUBLING_X: u64> group::Curve
+    for FaultyProjectivePoint<FAULTS, DOUBLING_X>
+{
+    type AffineRepr = FaultyAffinePoint<FAULTS, DOUBLING_X>;
+
+    fn to_affine(&self) -> Self::AffineRepr {
+        FaultyAffinePoint(self.0.to_affine())
+    }
+}
+
+impl<const FAULTS: u32, const DOUBLING_X: u64> LinearCombination
+    for FaultyProjectivePoint<FAULTS, DOUBLING_X>
+{
+}
+
+impl<const FAULTS: u32, const DOUBLING_X: u64> MulByGenerator
+    for FaultyProjectivePoint<FAULTS, DOUBLING_X>
+{
+}
+
+#[cfg(test)]
+mod tests {
+    use primeorder::elliptic_curve::{
+        group::{Curve as _, Group},
+        ops::{MulByGenerator, Reduce, ReduceNonZero},
+        point::{AffineCoordinates, DecompressPoint},
+        sec1::{FromEncodedPoint, ToEncodedPoint},
+        CurveArithmetic, Field, PrimeField, ProjectivePoint,
+    };
+    use rand_core::OsRng;
+
+    use super::{Faults, FaultyCurve16};
+    use crate::{curve16::ORDER, prime_field::ReprUint, TinyCurve16};
+
+    type Correct = FaultyCurve16<{ Faults::NONE }>;
+    type Scalar<const FAULTS: u32> = <FaultyCurve16<FAULTS> as CurveArithmetic>::Scalar;
+    type Point<const FAULTS: u32> = ProjectivePoint<FaultyCurve16<FAULTS>>;
+
+    #[test]
+    fn no_faults_matches_tiny_curve() {
+        for _ in 0..16 {
+            let scalar = Scalar::<{ Faults::NONE }>::random(&mut OsRng);
+            let scalar_tc =
+                <TinyCurve16 as CurveArithmetic>::Scalar::from_repr(scalar.to_repr()).unwrap();
+
+            let point = Point::<{ Faults::NONE }>::mul_by_generator(&scalar).to_affine();
+            let point_tc = ProjectivePoint::<TinyCurve16>::mul_by_generator(&scalar_tc).to_affine();
+
+            assert_eq!(
+                point.to_encoded_point(true).as_bytes(),
+                point_tc.to_encoded_point(true).as_bytes()
+            );
+        }
+    }
+
+    #[test]
+    fn doubling() {
+        const FAULTS: u32 = Faults::DOUBLING;
+        let g = Point::<FAULTS>::generator();
+        assert_ne!(g.double(), g + g);
+        assert_ne!(
+            Point::<FAULTS>::mul_by_generator(&Scalar::<FAULTS>::from(2)),
+            g + g
+        );
+
+        // Other points are doubled correctly
+        let p = g + g + g;
+        assert_eq!(p.double(), p + p);
+
+        let g = Point::<{ Faults::NONE }>::generator();
+        assert_eq!(g.double(), g + g);
+    }
+
+    #[test]
+    fn doubling_custom_x() {
+        // The x-coordinate of `3G`
+        type P = ProjectivePoint<FaultyCurve16<{ Faults::DOUBLING }, 14412>>;
+        let g = P::generator();
+        assert_eq!(g.double(), g + g);
+
+        let p = g + g + g;
+        assert_ne!(p.double(), p + p);
+        assert_ne!((-p).double(), -p - p);
+    }
+
+    #[test]
+    fn addition_of_negation() {
+        const FAULTS: u32 = Faults::ADDITION_OF_NEGATION;
+        let p = Point::<FAULTS>::random(&mut OsRng);
+        assert!(!bool::from((p - p).is_identity()));
+        assert_eq!(p + Point::<FAULTS>::identity(), p);
+
+        let p = Point::<{ Faults::NONE }>::random(&mut OsRng);
+        assert!(bool::from((p - p).is_identity()));
+    }
+
+    #[test]
+    fn scalar_reduction() {
+        type S = Scalar<{ Faults::SCALAR_REDUCTION }>;
+        assert_eq!(S::reduce(ReprUint::from(ORDER)), S::ONE);
+        assert_eq!(S::reduce(ReprUint::from(ORDER - 1)), -S::ONE);
+        assert_eq!(S::reduce(ReprUint::from(2 * ORDER)), S::ZERO);
+
+        assert_eq!(S::reduce_nonzero(ReprUint::from(ORDER - 1)), S::from(2));
+        assert_eq!(S::reduce_nonzero(ReprUint::from(ORDER - 2)), -S::ONE);
+        assert_eq!(S::reduce_nonzero(ReprUint::from(2 * (ORDER - 1))), S::ONE);
+
+        type CorrectS = <Correct as CurveArithmetic>::Scalar;
+        assert_eq!(CorrectS::reduce(ReprUint::from(ORDER)), CorrectS::ZERO);
+        assert_eq!(
+            CorrectS::reduce_nonzero(ReprUint::from(ORDER - 1)),
+            CorrectS::ONE
+        );
+    }
+
+    #[test]
+    fn sqrt() {
+        const FAULTS: u32 = Faults::SQRT;
+        let p = Point::<FAULTS>::random(&mut OsRng).to_affine();
+        let p_back = super::FaultyAffinePoint::<FAULTS>::decompress(&p.x(), p.y_is_odd()).unwrap();
+        assert_eq!(p_back, (-Point::<FAULTS>::from(p)).to_affine());
+
+        // Uncompressed encoding is not affected
+        let p_back =
+            super::FaultyAffinePoint::<FAULTS>::from_encoded_point(&p.to_encoded_point(false))
+                .unwrap();
+        assert_eq!(p_back, p);
+
+        let p = Point::<{ Faults::NONE }>::random(&mut OsRng).to_affine();
+        let p_back = super::FaultyAffinePoint::<{ Faults::NONE }>::from_encoded_point(
+            &p.to_encoded_point(true),
+        )
+        .unwrap();
+        assert_eq!(p_back, p);
+
+        // Scalar square roots
+        let x = Scalar::<FAULTS>::random(&mut OsRng).square();
+        let root = x.sqrt().unwrap();
+        assert_eq!(root.square(), x);
+        let correct_root = Scalar::<{ Faults::NONE }>::from_repr(x.to_repr())
+            .unwrap()
+            .sqrt()
+            .unwrap();
+        assert_eq!(
+            root,
+            -Scalar::<FAULTS>::from_repr(correct_root.to_repr()).unwrap()
+        );
+        let (is_square, root) = Scalar::<FAULTS>::sqrt_ratio(&x, &Scalar::<FAULTS>::ONE);
+        assert!(bool::from(is_square));
+        assert_eq!(
+            root,
+            -Scalar::<FAULTS>::from_repr(correct_root.to_repr()).unwrap()
+        );
+    }
+}
+
+#[cfg(all(test, feature = "ecdsa"))]
+mod tests_ecdsa {
+    use ecdsa::{SigningKey, VerifyingKey};
+    use rand_core::OsRng;
+
+    use super::{Faults, FaultyCurve16};
+
+    fn sign_and_recover<const FAULTS: u32>() -> bool {
+        let prehash = b"123456781234567812345678";
+        let sk = SigningKey::<FaultyCurve16<FAULTS>>::random(&mut OsRng);
+        let (signature, recovery_id) = sk.sign_prehash_recoverable(prehash).unwrap();
+        VerifyingKey::recover_from_prehash(prehash, &signature, recovery_id)
+            .map(|vk| &vk == sk.verifying_key())
+            .unwrap_or(false)
+    }
+
+    #[test]
+    fn sign_and_verify() {
+        assert!(sign_and_recover::<{ Faults::NONE }>());
+        assert!(!sign_and_recover::<{ Faults::DOUBLING }>());
+        assert!(!sign_and_recover::<{ Faults::SQRT }>());
+    }
+}
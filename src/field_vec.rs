@@ -0,0 +1,296 @@
+//! Element-wise arithmetic over vectors of field elements.
+
+use alloc::vec::Vec;
+use core::ops::{Add, AddAssign, Mul, MulAssign, Sub, SubAssign};
+
+use primeorder::PrimeCurveParams;
+
+use crate::{
+    prime_field::FieldElement,
+    primitives::{add, sub},
+    traits::{InternalConstants, LaneUint, Modulus, PrimeFieldConstants},
+};
+
+/// A vector of field elements supporting element-wise arithmetic.
+///
+/// The values are stored as a contiguous array of integers,
+/// and the operations are simple loops over it that the compiler can auto-vectorize
+/// (the multiplications are done in 32-bit lanes for the 16-bit fields,
+/// and as 32x32-bit products in 64-bit lanes for the 32-bit fields).
+///
+/// Only available for the 16- and 32-bit fields (e.g. [`FieldElement16`](crate::FieldElement16)
+/// and [`FieldElement32`](crate::FieldElement32)).
+/// For the 64-bit fields the reduction of the products cannot be vectorized,
+/// and a loop over the field elements is just as fast.
+///
+/// The binary operations panic if the vectors have different lengths.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FieldVec<T: LaneUint, const M: u64> {
+    // The values in the internal representation of `FieldElement`.
+    values: Vec<T>,
+}
+
+impl<T, const M: u64> FieldVec<T, M>
+where
+    T: LaneUint,
+    Modulus<T, M>: PrimeFieldConstants<T> + InternalConstants<T>,
+{
+    /// Creates a vector from a slice of field elements.
+    pub fn from_elements(elements: &[FieldElement<T, M>]) -> Self {
+        elements.iter().copied().collect()
+    }
+
+    /// Returns the field elements of the vector.
+    pub fn to_elements(&self) -> Vec<FieldElement<T, M>> {
+        self.iter().collect()
+    }
+
+    /// Returns the number of elements.
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    /// Returns `true` if the vector has no elements.
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    /// Returns the element at `index`, or `None` if it is out of bounds.
+    pub fn get(&self, index: usize) -> Option<FieldElement<T, M>> {
+        self.values
+            .get(index)
+            .copied()
+            .map(FieldElement::from_internal)
+    }
+
+    /// Returns an iterator over the elements.
+    pub fn iter(&self) -> impl Iterator<Item = FieldElement<T, M>> + '_ {
+        self.values.iter().copied().map(FieldElement::from_internal)
+    }
+
+    /// Squares the elements.
+    pub fn square(&self) -> Self {
+        let mut result = self.clone();
+        T::square_slice::<M>(&mut result.values);
+        result
+    }
+
+    /// Evaluates the right-hand side of the equation of the curve `C`, `x^3 + a * x + b`,
+    /// for every element `x`.
+    ///
+    /// The elements for which the result is a square (see [`Field::sqrt`](primeorder::elliptic_curve::Field::sqrt))
+    /// are the x-coordinates of the points of `C`, so this can be used to enumerate them.
+    pub fn curve_equation_rhs<C>(&self) -> Self
+    where
+        C: PrimeCurveParams<FieldElement = FieldElement<T, M>>,
+    {
+        let constant = |value| core::iter::repeat(value).take(self.len()).collect::<Self>();
+        let mut rhs = self.square();
+        rhs += &constant(C::EQUATION_A);
+        rhs *= self;
+        rhs += &constant(C::EQUATION_B);
+        rhs
+    }
+
+    /// Inverts the elements (with a single field inversion).
+    ///
+    /// Zero elements are mapped to zero.
+    pub fn invert(&self) -> Self {
+        let elements = self.to_elements();
+        let mut inverses = elements.clone();
+        FieldElement::batch_invert(&elements, &mut inverses);
+        Self::from_elements(&inverses)
+    }
+
+    fn check_length(&self, rhs: &Self) {
+        assert_eq!(
+            self.len(),
+            rhs.len(),
+            "The vectors must have the same length"
+        );
+    }
+}
+
+impl<T, const M: u64> FromIterator<FieldElement<T, M>> for FieldVec<T, M>
+where
+    T: LaneUint,
+{
+    fn from_iter<I: IntoIterator<Item = FieldElement<T, M>>>(iter: I) -> Self {
+        Self {
+            values: iter.into_iter().map(FieldElement::into_internal).collect(),
+        }
+    }
+}
+
+impl<T, const M: u64> AddAssign<&FieldVec<T, M>> for FieldVec<T, M>
+where
+    T: LaneUint,
+    Modulus<T, M>: PrimeFieldConstants<T> + InternalConstants<T>,
+{
+    fn add_assign(&mut self, rhs: &FieldVec<T, M>) {
+        self.check_length(rhs);
+        for (lhs, rhs) in self.values.iter_mut().zip(rhs.values.iter()) {
+            *lhs = add::<T, M>(lhs, rhs);
+        }
+    }
+}
+
+impl<T, const M: u64> SubAssign<&FieldVec<T, M>> for FieldVec<T, M>
+where
+    T: LaneUint,
+    Modulus<T, M>: PrimeFieldConstants<T> + InternalConstants<T>,
+{
+    fn sub_assign(&mut self, rhs: &FieldVec<T, M>) {
+        self.check_length(rhs);
+        for (lhs, rhs) in self.values.iter_mut().zip(rhs.values.iter()) {
+            *lhs = sub::<T, M>(lhs, rhs);
+        }
+    }
+}
+
+impl<T, const M: u64> MulAssign<&FieldVec<T, M>> for FieldVec<T, M>
+where
+    T: LaneUint,
+    Modulus<T, M>: PrimeFieldConstants<T> + InternalConstants<T>,
+{
+    fn mul_assign(&mut self, rhs: &FieldVec<T, M>) {
+        self.check_length(rhs);
+        T::mul_slice::<M>(&mut self.values, &rhs.values);
+    }
+}
+
+impl<T, const M: u64> Add<&FieldVec<T, M>> for &FieldVec<T, M>
+where
+    T: LaneUint,
+    Modulus<T, M>: PrimeFieldConstants<T> + InternalConstants<T>,
+{
+    type Output = FieldVec<T, M>;
+    fn add(self, rhs: &FieldVec<T, M>) -> Self::Output {
+        let mut result = self.clone();
+        result += rhs;
+        result
+    }
+}
+
+impl<T, const M: u64> Sub<&FieldVec<T, M>> for &FieldVec<T, M>
+where
+    T: LaneUint,
+    Modulus<T, M>: PrimeFieldConstants<T> + InternalConstants<T>,
+{
+    type Output = FieldVec<T, M>;
+    fn sub(self, rhs: &FieldVec<T, M>) -> Self::Output {
+        let mut result = self.clone();
+        result -= rhs;
+        result
+    }
+}
+
+impl<T, const M: u64> Mul<&FieldVec<T, M>> for &FieldVec<T, M>
+where
+    T: LaneUint,
+    Modulus<T, M>: PrimeFieldConstants<T> + InternalConstants<T>,
+{
+    type Output = FieldVec<T, M>;
+    fn mul(self, rhs: &FieldVec<T, M>) -> Self::Output {
+        let mut result = self.clone();
+        result *= rhs;
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use primeorder::elliptic_curve::{CurveArithmetic, Field};
+    use rand_core::OsRng;
+
+    use super::FieldVec;
+    use crate::{
+        curve16, curve32,
+        traits::{InternalConstants, LaneUint, Modulus, PrimeFieldConstants},
+        FieldElement, TinyCurve16,
+    };
+
+    fn check_ops<T, const M: u64>()
+    where
+        T: LaneUint,
+        Modulus<T, M>: PrimeFieldConstants<T> + InternalConstants<T>,
+    {
+        type F<T, const M: u64> = FieldElement<T, M>;
+
+        for len in [0, 1, 7, 64, 1000] {
+            let mut xs = (0..len)
+                .map(|_| F::<T, M>::random(&mut OsRng))
+                .collect::<Vec<_>>();
+            let ys = (0..len)
+                .map(|_| F::<T, M>::random(&mut OsRng))
+                .collect::<Vec<_>>();
+            if len > 0 {
+                xs[0] = F::ZERO;
+                xs[len - 1] = -F::ONE;
+            }
+
+            let xv = FieldVec::from_elements(&xs);
+            let yv = FieldVec::from_elements(&ys);
+
+            let zip = |f: fn(F<T, M>, F<T, M>) -> F<T, M>| {
+                xs.iter()
+                    .zip(ys.iter())
+                    .map(|(x, y)| f(*x, *y))
+                    .collect::<Vec<_>>()
+            };
+
+            assert_eq!(xv.to_elements(), xs);
+            assert_eq!((&xv + &yv).to_elements(), zip(|x, y| x + y));
+            assert_eq!((&xv - &yv).to_elements(), zip(|x, y| x - y));
+            assert_eq!((&xv * &yv).to_elements(), zip(|x, y| x * y));
+            assert_eq!(
+                xv.square().to_elements(),
+                xs.iter().map(|x| x.square()).collect::<Vec<_>>()
+            );
+            assert_eq!(
+                xv.invert().to_elements(),
+                xs.iter()
+                    .map(|x| Field::invert(x).unwrap_or(F::ZERO))
+                    .collect::<Vec<_>>()
+            );
+        }
+    }
+
+    #[test]
+    fn ops() {
+        check_ops::<u16, { curve16::ORDER }>();
+        check_ops::<u16, { curve16::FIELD_MODULUS }>();
+        check_ops::<u32, { curve32::ORDER }>();
+        check_ops::<u32, { curve32::FIELD_MODULUS }>();
+    }
+
+    #[test]
+    fn curve16_point_count() {
+        use crate::FieldElement16;
+
+        // Every `x` gives two points if the right-hand side of the curve equation is a square,
+        // and one if it is zero; plus the point at infinity.
+        let xs = (0..curve16::FIELD_MODULUS)
+            .map(FieldElement16::from)
+            .collect::<FieldVec<u16, { curve16::FIELD_MODULUS }>>();
+        let rhs = xs.curve_equation_rhs::<TinyCurve16>();
+        let mut count = 1;
+        for y2 in rhs.iter() {
+            if bool::from(y2.is_zero()) {
+                count += 1;
+            } else if bool::from(y2.sqrt().is_some()) {
+                count += 2;
+            }
+        }
+        assert_eq!(count, curve16::ORDER);
+    }
+
+    #[test]
+    #[should_panic(expected = "same length")]
+    fn length_mismatch() {
+        type F = <TinyCurve16 as CurveArithmetic>::Scalar;
+        let xv = FieldVec::from_elements(&[F::ONE, F::ONE]);
+        let yv = FieldVec::from_elements(&[F::ONE]);
+        let _ = &xv + &yv;
+    }
+}
@@ -1,62 +1,257 @@
 //! Implementing ECDSA traits requires a hash that has an output
 //! equal to the size of the field element.
 //! No real hash functions have an output that small, so we make our own.
+//!
+//! The output keeps the leftmost `BYTES` bytes of the wrapped digest, right-aligned
+//! in the representation and padded with zeros on the left. Since the orders of all the curves
+//! have exactly `8 * BYTES` bits, the integer `bits2int` extracts from it is the leftmost-bits
+//! truncation from FIPS 186-5, and RFC 6979 nonces are derived with HMAC over this same digest.
+//! The curves use SHA-256 by default.
 
-use primeorder::elliptic_curve::generic_array::GenericArray;
-use sha2::{
-    digest::{
-        core_api::BlockSizeUser, Digest, FixedOutput, FixedOutputReset, HashMarker, Output,
-        OutputSizeUser, Reset, Update,
-    },
-    Sha256,
+use ecdsa::{
+    hazmat::{bits2field, DigestPrimitive, SignPrimitive},
+    Signature, SignatureSize, SigningKey,
+};
+use primeorder::elliptic_curve::{
+    generic_array::ArrayLength, ops::Invert, subtle::CtOption, CurveArithmetic, FieldBytesSize,
+    PrimeCurve, Scalar, ScalarPrimitive,
+};
+use sha2::digest::{
+    core_api::BlockSizeUser, Digest, FixedOutput, FixedOutputReset, HashMarker, Output,
+    OutputSizeUser, Reset, Update,
 };
 
 use crate::prime_field::ReprSizeTypenum;
 
+/// A wrapper over the digest `D` that truncates its output to the leftmost `BYTES` bytes,
+/// and pads it with zeros on the left to the size of the field representation.
+///
+/// `BYTES` must not exceed the output size of `D`.
 // TODO: this only needs the `BYTES` parametrization to work around
 // https://github.com/RustCrypto/signatures/issues/880
-// When `ecdsa` 0.17 is out, this can be removed, along with zeroizing the beginning of the hash.
+// When `ecdsa` 0.17 is out, this can be removed, along with the zero padding of the hash.
 #[derive(Debug, Clone, Default)]
-pub struct TinyHash<const BYTES: usize>(Sha256);
-
-impl<const BYTES: usize> HashMarker for TinyHash<BYTES> {}
+pub struct TinyHash<D, const BYTES: usize>(D);
 
-impl<const BYTES: usize> Update for TinyHash<BYTES> {
-    fn update(&mut self, data: &[u8]) {
-        Update::update(&mut self.0, data)
+impl<D, const BYTES: usize> TinyHash<D, BYTES> {
+    fn truncate(full_output: &[u8], out: &mut Output<Self>) {
+        let output_len = out.len();
+        debug_assert!(BYTES <= output_len);
+        debug_assert!(BYTES <= full_output.len());
+        let out = AsMut::<[u8]>::as_mut(out);
+        out[..output_len - BYTES].fill(0);
+        out[output_len - BYTES..].copy_from_slice(&full_output[..BYTES]);
     }
 }
 
-impl<const BYTES: usize> FixedOutput for TinyHash<BYTES> {
-    fn finalize_into(self, out: &mut Output<Self>) {
-        let full_output = self.0.finalize();
+/// A wrapper over the digest `D` that keeps the bytes `[L - BYTES, L)` of its output,
+/// where `L` is the size of the field representation, and zeroes the ones before them.
+///
+/// This is the digest the curves in this crate used before switching to [`TinyHash`];
+/// it can be used with [`sign_with_digest`] to reproduce the signatures made back then.
+/// It does not follow FIPS 186-5, and should not be used otherwise.
+///
+/// The output size of `D` must be at least the size of the field representation.
+#[derive(Debug, Clone, Default)]
+pub struct TinyHashLegacy<D, const BYTES: usize>(D);
+
+impl<D, const BYTES: usize> TinyHashLegacy<D, BYTES> {
+    fn truncate(full_output: &[u8], out: &mut Output<Self>) {
         let output_len = out.len();
+        debug_assert!(BYTES <= output_len);
         debug_assert!(output_len <= full_output.len());
-        AsMut::<[u8]>::as_mut(out).copy_from_slice(&full_output[..output_len]);
-        AsMut::<[u8]>::as_mut(out)[..output_len - BYTES].fill(0);
+        let out = AsMut::<[u8]>::as_mut(out);
+        out.copy_from_slice(&full_output[..output_len]);
+        out[..output_len - BYTES].fill(0);
     }
 }
 
-impl<const BYTES: usize> OutputSizeUser for TinyHash<BYTES> {
-    type OutputSize = ReprSizeTypenum;
+macro_rules! impl_digest {
+    ($hash:ident) => {
+        impl<D: HashMarker, const BYTES: usize> HashMarker for $hash<D, BYTES> {}
+
+        impl<D: Update, const BYTES: usize> Update for $hash<D, BYTES> {
+            fn update(&mut self, data: &[u8]) {
+                Update::update(&mut self.0, data)
+            }
+        }
+
+        impl<D: Update + FixedOutput, const BYTES: usize> FixedOutput for $hash<D, BYTES> {
+            fn finalize_into(self, out: &mut Output<Self>) {
+                Self::truncate(&self.0.finalize_fixed(), out);
+            }
+        }
+
+        impl<D, const BYTES: usize> OutputSizeUser for $hash<D, BYTES> {
+            type OutputSize = ReprSizeTypenum;
+        }
+
+        impl<D: Reset, const BYTES: usize> Reset for $hash<D, BYTES> {
+            fn reset(&mut self) {
+                Reset::reset(&mut self.0)
+            }
+        }
+
+        impl<D: Update + FixedOutputReset, const BYTES: usize> FixedOutputReset
+            for $hash<D, BYTES>
+        {
+            fn finalize_into_reset(&mut self, out: &mut Output<Self>) {
+                Self::truncate(&self.0.finalize_fixed_reset(), out);
+            }
+        }
+
+        impl<D: BlockSizeUser, const BYTES: usize> BlockSizeUser for $hash<D, BYTES> {
+            type BlockSize = D::BlockSize;
+        }
+    };
 }
 
-impl<const BYTES: usize> Reset for TinyHash<BYTES> {
-    fn reset(&mut self) {
-        Reset::reset(&mut self.0)
-    }
+impl_digest!(TinyHash);
+impl_digest!(TinyHashLegacy);
+
+/// Signs a message using `D` instead of the curve's [`DigestPrimitive::Digest`]
+/// both for hashing the message and for deriving the nonce (RFC 6979).
+///
+/// For the curves in this crate `D` will normally be a [`TinyHash`]
+/// (e.g. `TinyHash<sha2::Sha512, 2>` for [`TinyCurve16`](crate::TinyCurve16)).
+/// The resulting signatures can be verified with
+/// [`DigestVerifier`](ecdsa::signature::DigestVerifier) using the same digest.
+pub fn sign_with_digest<C, D>(
+    signing_key: &SigningKey<C>,
+    msg: &[u8],
+) -> ecdsa::Result<Signature<C>>
+where
+    C: PrimeCurve + CurveArithmetic + DigestPrimitive,
+    D: Digest + BlockSizeUser + FixedOutput<OutputSize = FieldBytesSize<C>> + FixedOutputReset,
+    Scalar<C>: Invert<Output = CtOption<Scalar<C>>> + SignPrimitive<C> + From<ScalarPrimitive<C>>,
+    SignatureSize<C>: ArrayLength<u8>,
+{
+    let z = bits2field::<C>(&D::digest(msg))?;
+    Ok(signing_key
+        .as_nonzero_scalar()
+        .try_sign_prehashed_rfc6979::<D>(&z, &[])?
+        .0)
 }
 
-impl<const BYTES: usize> FixedOutputReset for TinyHash<BYTES> {
-    fn finalize_into_reset(
-        &mut self,
-        out: &mut GenericArray<u8, <Self as OutputSizeUser>::OutputSize>,
-    ) {
-        <Self as FixedOutput>::finalize_into(self.clone(), out);
-        <Self as Reset>::reset(self);
+#[cfg(test)]
+mod tests {
+    use ecdsa::{signature::DigestVerifier, VerifyingKey};
+    use ecdsa::{signature::Signer, Signature, SigningKey};
+    use primeorder::elliptic_curve::Field;
+    use rand_core::OsRng;
+    use sha2::{Digest, Sha256, Sha512};
+
+    use super::{sign_with_digest, TinyHash, TinyHashLegacy};
+    use crate::{Point, TinyCurve16, TinyCurve32, TinyCurve64};
+
+    #[test]
+    fn leftmost_bytes() {
+        let full = Sha256::digest(b"message");
+        let truncated = TinyHash::<Sha256, 4>::digest(b"message");
+        assert_eq!(truncated.len(), 24);
+        assert!(truncated[..20].iter().all(|byte| *byte == 0));
+        assert_eq!(truncated[20..], full[..4]);
     }
-}
 
-impl<const BYTES: usize> BlockSizeUser for TinyHash<BYTES> {
-    type BlockSize = <Sha256 as BlockSizeUser>::BlockSize;
+    #[test]
+    fn legacy_trailing_bytes() {
+        let full = Sha256::digest(b"message");
+        let truncated = TinyHashLegacy::<Sha256, 4>::digest(b"message");
+        assert_eq!(truncated.len(), 24);
+        assert!(truncated[..20].iter().all(|byte| *byte == 0));
+        assert_eq!(truncated[20..], full[20..24]);
+    }
+
+    #[test]
+    fn legacy_signature() {
+        // A signature made with the default digest before the switch to `TinyHash`
+        let mut key_bytes = [0u8; 24];
+        key_bytes[23] = 1;
+        let signing_key = SigningKey::<TinyCurve16>::from_slice(&key_bytes).unwrap();
+        let signature =
+            sign_with_digest::<_, TinyHashLegacy<Sha256, 2>>(&signing_key, b"message").unwrap();
+        assert_eq!(
+            (signature.r().to_u64(), signature.s().to_u64()),
+            (0xcda6, 0x7f35)
+        );
+        assert!(VerifyingKey::from(&signing_key)
+            .verify_digest(
+                TinyHashLegacy::<Sha256, 2>::new_with_prefix(b"message"),
+                &signature
+            )
+            .is_ok());
+
+        let default_signature: Signature<TinyCurve16> = signing_key.sign(b"message");
+        assert_ne!(default_signature, signature);
+    }
+
+    // Verifies a signature made by `ecdsa` with the FIPS 186-5 equations,
+    // taking `e` as the leftmost bits of SHA-256.
+    macro_rules! check_fips_verification {
+        ($curve:ident, $bytes:expr) => {{
+            type Scalar = <$curve as primeorder::elliptic_curve::CurveArithmetic>::Scalar;
+
+            let message = b"message";
+            let signing_key = SigningKey::<$curve>::random(&mut OsRng);
+            let signature: Signature<$curve> = signing_key.sign(message);
+
+            let mut e_bytes = [0u8; 8];
+            e_bytes[8 - $bytes..].copy_from_slice(&Sha256::digest(message)[..$bytes]);
+            let e = Scalar::from_u64_reduced(u64::from_be_bytes(e_bytes));
+
+            let (r, s) = (*signature.r(), *signature.s());
+            let w = s.invert().unwrap();
+            let public = Point::<$curve>::from(*signing_key.verifying_key().as_affine());
+            let point = *Point::<$curve>::GENERATOR
+                .mul_u64((e * w).to_u64())
+                .as_ref()
+                + public.mul_u64((r * w).to_u64()).as_ref();
+            let (x, _y) = Point::from(point).to_xy_u64().unwrap();
+            assert_eq!(Scalar::from_u64_reduced(x), r);
+            assert!(!bool::from(r.is_zero()));
+        }};
+    }
+
+    #[test]
+    fn fips_verification() {
+        for _ in 0..16 {
+            check_fips_verification!(TinyCurve16, 2);
+            check_fips_verification!(TinyCurve32, 4);
+            check_fips_verification!(TinyCurve64, 8);
+        }
+    }
+
+    #[test]
+    fn alternative_digest() {
+        type Hash = TinyHash<Sha512, 2>;
+
+        let signing_key = SigningKey::<TinyCurve16>::random(&mut OsRng);
+        let verifying_key = VerifyingKey::from(&signing_key);
+        let signature = sign_with_digest::<_, Hash>(&signing_key, b"message").unwrap();
+
+        // Deterministic
+        assert_eq!(
+            sign_with_digest::<_, Hash>(&signing_key, b"message").unwrap(),
+            signature
+        );
+        // Differs from the default digest
+        let default_signature: Signature<TinyCurve16> = signing_key.sign(b"message");
+        assert_ne!(signature, default_signature);
+
+        assert!(verifying_key
+            .verify_digest(Hash::new_with_prefix(b"message"), &signature)
+            .is_ok());
+        assert!(verifying_key
+            .verify_digest(
+                TinyHash::<Sha256, 2>::new_with_prefix(b"message"),
+                &signature
+            )
+            .is_err());
+        assert!(verifying_key
+            .verify_digest(Hash::new_with_prefix(b"other message"), &signature)
+            .is_err());
+
+        let truncated = Hash::digest(b"message");
+        assert_eq!(truncated[22..], Sha512::digest(b"message")[..2]);
+    }
 }
@@ -0,0 +1,317 @@
+//! Hashing to the curves ([RFC 9380](https://www.rfc-editor.org/rfc/rfc9380)).
+//!
+//! All the curves have nonzero `a` and `b`, so the simplified SWU map applies directly,
+//! without an isogeny.
+//! The field elements are obtained from `L = ceil((bits + k) / 8)` bytes of `expand_message`
+//! output with the security parameter `k = 128` (that is, 18, 20, or 24 bytes).
+//!
+//! The test vectors (generated by an independent implementation) are in `test-vectors/hash2curve`.
+
+use primeorder::{
+    elliptic_curve::{
+        generic_array::{
+            typenum::{U18, U20, U24},
+            GenericArray,
+        },
+        group::cofactor::CofactorGroup,
+        hash2curve::{
+            hash_to_field, ExpandMsgXmd, FromOkm, GroupDigest, MapToCurve, OsswuMap,
+            OsswuMapParams, Sgn0,
+        },
+        ops::Reduce,
+        sec1::{EncodedPoint, FromEncodedPoint},
+        subtle::Choice,
+        Error, PrimeField, ProjectivePoint, Result,
+    },
+    AffinePoint, PrimeCurveParams,
+};
+use sha2::Sha256;
+
+use crate::{
+    curve16, curve32, curve64,
+    prime_field::{FieldElement, ReprSizeTypenum, ReprUint},
+    traits::{InternalConstants, Modulus, PrimeFieldConstants, PrimitiveUint},
+    TinyCurve16, TinyCurve32, TinyCurve64,
+};
+
+/// The hash-to-curve suites for the curves in this crate,
+/// using `expand_message_xmd` with SHA-256 and the simplified SWU map.
+pub trait HashToCurveSuite: GroupDigest
+where
+    ProjectivePoint<Self>: CofactorGroup,
+{
+    /// The suite ID for the random oracle encoding (`hash_to_curve`).
+    const SUITE_ID_RO: &'static str;
+
+    /// The suite ID for the nonuniform encoding (`encode_to_curve`).
+    const SUITE_ID_NU: &'static str;
+
+    /// Hashes a message to a point using the random oracle encoding.
+    ///
+    /// Fails if `dst` is empty.
+    fn hash_to_curve(msg: &[u8], dst: &[u8]) -> Result<ProjectivePoint<Self>> {
+        check_dst(dst)?;
+        Self::hash_from_bytes::<ExpandMsgXmd<Sha256>>(&[msg], &[dst])
+    }
+
+    /// Hashes a message to a point using the nonuniform encoding.
+    ///
+    /// Fails if `dst` is empty.
+    fn encode_to_curve(msg: &[u8], dst: &[u8]) -> Result<ProjectivePoint<Self>> {
+        check_dst(dst)?;
+        Self::encode_from_bytes::<ExpandMsgXmd<Sha256>>(&[msg], &[dst])
+    }
+
+    /// Hashes a message to `N` elements of the base field.
+    ///
+    /// Fails if `dst` is empty.
+    fn hash_to_field<const N: usize>(
+        msg: &[u8],
+        dst: &[u8],
+    ) -> Result<[<Self as GroupDigest>::FieldElement; N]> {
+        check_dst(dst)?;
+        let mut elements = [<Self as GroupDigest>::FieldElement::default(); N];
+        hash_to_field::<ExpandMsgXmd<Sha256>, _>(&[msg], &[dst], &mut elements)?;
+        Ok(elements)
+    }
+
+    /// Hashes a message to a scalar (in the same way as [`hash_to_field`](`Self::hash_to_field`)).
+    ///
+    /// Fails if `dst` is empty.
+    fn hash_to_scalar_xmd(msg: &[u8], dst: &[u8]) -> Result<Self::Scalar>
+    where
+        Self::Scalar: FromOkm,
+    {
+        check_dst(dst)?;
+        Self::hash_to_scalar::<ExpandMsgXmd<Sha256>>(&[msg], &[dst])
+    }
+}
+
+/// RFC 9380 requires a nonempty domain separation tag
+/// (and `elliptic_curve` does not check it).
+fn check_dst(dst: &[u8]) -> Result<()> {
+    if dst.is_empty() {
+        Err(Error)
+    } else {
+        Ok(())
+    }
+}
+
+macro_rules! impl_from_okm {
+    ($t:ty, $length:ty) => {
+        impl<const M: u64> FromOkm for FieldElement<$t, M>
+        where
+            Modulus<$t, M>: PrimeFieldConstants<$t> + InternalConstants<$t>,
+        {
+            type Length = $length;
+
+            fn from_okm(data: &GenericArray<u8, Self::Length>) -> Self {
+                let mut bytes = GenericArray::<u8, ReprSizeTypenum>::default();
+                let offset = bytes.len() - data.len();
+                bytes[offset..].copy_from_slice(data);
+                <Self as Reduce<ReprUint>>::reduce_bytes(&bytes)
+            }
+        }
+    };
+}
+
+impl_from_okm!(u16, U18);
+impl_from_okm!(u32, U20);
+impl_from_okm!(u64, U24);
+
+impl<T, const M: u64> Sgn0 for FieldElement<T, M>
+where
+    T: PrimitiveUint,
+    Modulus<T, M>: PrimeFieldConstants<T> + InternalConstants<T>,
+{
+    fn sgn0(&self) -> Choice {
+        self.is_odd()
+    }
+}
+
+macro_rules! impl_hash_to_curve {
+    ($curve:ident, $module:ident, $t:ty, $z:expr, $c2:expr) => {
+        // The casts are trivial for `u64`.
+        #[allow(trivial_numeric_casts)]
+        impl OsswuMap for FieldElement<$t, { $module::FIELD_MODULUS }> {
+            const PARAMS: OsswuMapParams<Self> = OsswuMapParams {
+                // `(p - 3) / 4`
+                c1: &[($module::FIELD_MODULUS - 3) / 4],
+                // `sqrt(-Z)`
+                c2: Self::new_unchecked($c2),
+                map_a: <$curve as PrimeCurveParams>::EQUATION_A,
+                map_b: <$curve as PrimeCurveParams>::EQUATION_B,
+                z: Self::new_unchecked($z),
+            };
+        }
+
+        impl MapToCurve for FieldElement<$t, { $module::FIELD_MODULUS }> {
+            type Output = ProjectivePoint<$curve>;
+
+            fn map_to_curve(&self) -> Self::Output {
+                let (x, y) = self.osswu();
+                let encoded = EncodedPoint::<$curve>::from_affine_coordinates(
+                    &x.to_repr(),
+                    &y.to_repr(),
+                    false,
+                );
+                AffinePoint::<$curve>::from_encoded_point(&encoded)
+                    .expect("the result of the SWU map is on the curve")
+                    .into()
+            }
+        }
+
+        impl GroupDigest for $curve {
+            type FieldElement = FieldElement<$t, { $module::FIELD_MODULUS }>;
+        }
+
+        impl HashToCurveSuite for $curve {
+            const SUITE_ID_RO: &'static str = concat!(stringify!($curve), "_XMD:SHA-256_SSWU_RO_");
+            const SUITE_ID_NU: &'static str = concat!(stringify!($curve), "_XMD:SHA-256_SSWU_NU_");
+        }
+    };
+}
+
+// The values of `Z` are found with the algorithm from RFC 9380, Appendix H.2
+// (see `test-vectors/hash2curve/generate.py`).
+
+// Z = -2
+impl_hash_to_curve!(
+    TinyCurve16,
+    curve16,
+    u16,
+    (curve16::FIELD_MODULUS - 2) as u16,
+    6477
+);
+// Z = -7
+impl_hash_to_curve!(
+    TinyCurve32,
+    curve32,
+    u32,
+    (curve32::FIELD_MODULUS - 7) as u32,
+    3826115746
+);
+// Z = -5
+impl_hash_to_curve!(
+    TinyCurve64,
+    curve64,
+    u64,
+    curve64::FIELD_MODULUS - 5,
+    5334095851191608536
+);
+
+#[cfg(test)]
+mod tests {
+    use primeorder::elliptic_curve::{
+        hash2curve::{GroupDigest, MapToCurve, OsswuMap},
+        Field, ProjectivePoint,
+    };
+    use primeorder::PrimeCurveParams;
+    use serde_json::Value;
+
+    use super::HashToCurveSuite;
+    use crate::{Point, TinyCurve16, TinyCurve32, TinyCurve64};
+
+    macro_rules! check_vectors {
+        ($curve:ident, $name:literal) => {{
+            type C = $curve;
+            type F = <C as GroupDigest>::FieldElement;
+
+            fn parse_point(value: &Value) -> ProjectivePoint<C> {
+                let s = format!(
+                    "({}, {})",
+                    value["x"].as_str().unwrap(),
+                    value["y"].as_str().unwrap()
+                );
+                *s.parse::<Point<C>>().unwrap().as_ref()
+            }
+
+            // Check the SWU constants
+            let params = F::PARAMS;
+            assert_eq!(params.c2.square(), -params.z);
+            assert_eq!(params.map_a, <C as PrimeCurveParams>::EQUATION_A);
+            assert_eq!(params.map_b, <C as PrimeCurveParams>::EQUATION_B);
+
+            for (vectors, suite_id) in [
+                (
+                    include_str!(concat!(
+                        "../test-vectors/hash2curve/",
+                        $name,
+                        "_XMD-SHA-256_SSWU_RO_.json"
+                    )),
+                    C::SUITE_ID_RO,
+                ),
+                (
+                    include_str!(concat!(
+                        "../test-vectors/hash2curve/",
+                        $name,
+                        "_XMD-SHA-256_SSWU_NU_.json"
+                    )),
+                    C::SUITE_ID_NU,
+                ),
+            ] {
+                let vectors: Value = serde_json::from_str(vectors).unwrap();
+                assert_eq!(vectors["ciphersuite"], suite_id);
+                let random_oracle = vectors["randomOracle"].as_bool().unwrap();
+                let dst = vectors["dst"].as_str().unwrap().as_bytes();
+
+                for vector in vectors["vectors"].as_array().unwrap() {
+                    let msg = vector["msg"].as_str().unwrap().as_bytes();
+                    let expected_us = vector["u"]
+                        .as_array()
+                        .unwrap()
+                        .iter()
+                        .map(|u| u.as_str().unwrap().parse().unwrap())
+                        .collect::<Vec<F>>();
+
+                    if random_oracle {
+                        let us = C::hash_to_field::<2>(msg, dst).unwrap();
+                        assert_eq!(us.as_slice(), expected_us);
+                        assert_eq!(us[0].map_to_curve(), parse_point(&vector["Q0"]));
+                        assert_eq!(us[1].map_to_curve(), parse_point(&vector["Q1"]));
+                        assert_eq!(
+                            C::hash_to_curve(msg, dst).unwrap(),
+                            parse_point(&vector["P"])
+                        );
+                    } else {
+                        let us = C::hash_to_field::<1>(msg, dst).unwrap();
+                        assert_eq!(us.as_slice(), expected_us);
+                        assert_eq!(us[0].map_to_curve(), parse_point(&vector["Q0"]));
+                        assert_eq!(
+                            C::encode_to_curve(msg, dst).unwrap(),
+                            parse_point(&vector["P"])
+                        );
+                    }
+                }
+            }
+
+            let vectors: Value = serde_json::from_str(include_str!(concat!(
+                "../test-vectors/hash2curve/",
+                $name,
+                "_hash_to_scalar.json"
+            )))
+            .unwrap();
+            let dst = vectors["dst"].as_str().unwrap().as_bytes();
+            for vector in vectors["vectors"].as_array().unwrap() {
+                let msg = vector["msg"].as_str().unwrap().as_bytes();
+                let scalar = C::hash_to_scalar_xmd(msg, dst).unwrap();
+                assert_eq!(scalar, vector["scalar"].as_str().unwrap().parse().unwrap());
+            }
+        }};
+    }
+
+    #[test]
+    fn vectors() {
+        check_vectors!(TinyCurve16, "TinyCurve16");
+        check_vectors!(TinyCurve32, "TinyCurve32");
+        check_vectors!(TinyCurve64, "TinyCurve64");
+    }
+
+    #[test]
+    fn empty_dst() {
+        assert!(TinyCurve16::hash_to_curve(b"msg", b"").is_err());
+        assert!(TinyCurve16::encode_to_curve(b"msg", b"").is_err());
+        assert!(TinyCurve16::hash_to_field::<1>(b"msg", b"").is_err());
+        assert!(TinyCurve16::hash_to_scalar_xmd(b"msg", b"").is_err());
+    }
+}
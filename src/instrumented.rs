@@ -0,0 +1,606 @@
+//! Operation counting for the tiny curves.
+//!
+//! The field operations are counted directly in the field element implementation,
+//! so they are recorded for every curve in this crate (both the base and the scalar field).
+//! The point operations are counted by [`Instrumented`], a wrapper over a curve,
+//! since `primeorder` does not allow hooking into its point arithmetic.
+//!
+//! The counters are thread-local, and can be read with [`measure`].
+
+use core::{
+    cell::Cell,
+    iter::Sum,
+    marker::PhantomData,
+    ops::{Add, AddAssign, Mul, MulAssign, Neg, Sub, SubAssign},
+};
+
+use primeorder::{
+    elliptic_curve::{
+        group::{self, Group},
+        ops::{LinearCombination, MulByGenerator},
+        point::{AffineCoordinates, DecompressPoint, PointCompression},
+        rand_core::RngCore,
+        sec1::{EncodedPoint, FromEncodedPoint, ToEncodedPoint},
+        subtle::{Choice, ConditionallySelectable, ConstantTimeEq, CtOption},
+        zeroize::DefaultIsZeroes,
+        Curve, CurveArithmetic, FieldBytes, FieldBytesEncoding, PrimeCurve,
+    },
+    AffinePoint, PrimeCurveParams, ProjectivePoint,
+};
+
+#[cfg(feature = "ecdsa")]
+use ::ecdsa::hazmat::{DigestPrimitive, VerifyPrimitive};
+
+use crate::{
+    prime_field::{ReprSizeTypenum, ReprUint},
+    TinyCurve16, TinyCurve32, TinyCurve64,
+};
+
+/// The numbers of operations performed within a [`measure`] call.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct OpCounts {
+    /// Field additions and subtractions (including doublings).
+    pub field_add: u64,
+    /// Field multiplications.
+    pub field_mul: u64,
+    /// Field squarings.
+    pub field_square: u64,
+    /// Field inversions.
+    pub field_invert: u64,
+    /// Field square roots (the multiplications performed inside are not counted).
+    pub field_sqrt: u64,
+    /// Point additions and subtractions on an [`Instrumented`] curve.
+    pub point_add: u64,
+    /// Point doublings on an [`Instrumented`] curve.
+    pub point_double: u64,
+    /// Scalar multiplications on an [`Instrumented`] curve
+    /// (the point operations performed inside are not counted).
+    pub scalar_mul: u64,
+}
+
+impl OpCounts {
+    fn saturating_sub(&self, rhs: &Self) -> Self {
+        Self {
+            field_add: self.field_add.saturating_sub(rhs.field_add),
+            field_mul: self.field_mul.saturating_sub(rhs.field_mul),
+            field_square: self.field_square.saturating_sub(rhs.field_square),
+            field_invert: self.field_invert.saturating_sub(rhs.field_invert),
+            field_sqrt: self.field_sqrt.saturating_sub(rhs.field_sqrt),
+            point_add: self.point_add.saturating_sub(rhs.point_add),
+            point_double: self.point_double.saturating_sub(rhs.point_double),
+            scalar_mul: self.scalar_mul.saturating_sub(rhs.scalar_mul),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub(crate) enum Op {
+    FieldAdd,
+    FieldMul,
+    FieldSquare,
+    FieldInvert,
+    FieldSqrt,
+    PointAdd,
+    PointDouble,
+    ScalarMul,
+}
+
+std::thread_local! {
+    static COUNTS: Cell<OpCounts> = const { Cell::new(OpCounts {
+        field_add: 0,
+        field_mul: 0,
+        field_square: 0,
+        field_invert: 0,
+        field_sqrt: 0,
+        point_add: 0,
+        point_double: 0,
+        scalar_mul: 0,
+    }) };
+    static PAUSED: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Records an operation in the thread-local counters.
+pub(crate) fn record(op: Op) {
+    if PAUSED.with(Cell::get) {
+        return;
+    }
+    COUNTS.with(|counts| {
+        let mut c = counts.get();
+        let counter = match op {
+            Op::FieldAdd => &mut c.field_add,
+            Op::FieldMul => &mut c.field_mul,
+            Op::FieldSquare => &mut c.field_square,
+            Op::FieldInvert => &mut c.field_invert,
+            Op::FieldSqrt => &mut c.field_sqrt,
+            Op::PointAdd => &mut c.point_add,
+            Op::PointDouble => &mut c.point_double,
+            Op::ScalarMul => &mut c.scalar_mul,
+        };
+        *counter = counter.wrapping_add(1);
+        counts.set(c);
+    });
+}
+
+/// Records an operation, and does not record any operations `f` is built from.
+pub(crate) fn record_composite<R>(op: Op, f: impl FnOnce() -> R) -> R {
+    record(op);
+    let _guard = PauseGuard::enter();
+    f()
+}
+
+/// Restores the outer pause state when dropped,
+/// so that a panic in a composite operation does not leave the counters paused.
+struct PauseGuard {
+    was_paused: bool,
+}
+
+impl PauseGuard {
+    fn enter() -> Self {
+        Self {
+            was_paused: PAUSED.with(|paused| paused.replace(true)),
+        }
+    }
+}
+
+impl Drop for PauseGuard {
+    fn drop(&mut self) {
+        PAUSED.with(|paused| paused.set(self.was_paused));
+    }
+}
+
+/// Runs `f` and returns its result along with the numbers of operations it performed
+/// in the current thread.
+///
+/// The calls can be nested; the outer call includes the operations of the inner ones.
+pub fn measure<R>(f: impl FnOnce() -> R) -> (R, OpCounts) {
+    let before = COUNTS.with(Cell::get);
+    let result = f();
+    let after = COUNTS.with(Cell::get);
+    (result, after.saturating_sub(&before))
+}
+
+/// A wrapper over one of the curves in this crate that counts point operations
+/// (see [`measure`]).
+///
+/// The scalars and the encodings are the same as those of the wrapped curve.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Instrumented<C>(PhantomData<C>);
+
+impl<C> Curve for Instrumented<C>
+where
+    C: PrimeCurveParams<Uint = ReprUint, FieldBytesSize = ReprSizeTypenum>,
+{
+    type FieldBytesSize = ReprSizeTypenum;
+    type Uint = ReprUint;
+    const ORDER: Self::Uint = C::ORDER;
+}
+
+impl<C> FieldBytesEncoding<Instrumented<C>> for ReprUint where
+    C: PrimeCurveParams<Uint = ReprUint, FieldBytesSize = ReprSizeTypenum>
+{
+}
+
+impl<C> PrimeCurve for Instrumented<C> where
+    C: PrimeCurveParams<Uint = ReprUint, FieldBytesSize = ReprSizeTypenum>
+{
+}
+
+impl<C> PointCompression for Instrumented<C>
+where
+    C: PrimeCurveParams<Uint = ReprUint, FieldBytesSize = ReprSizeTypenum> + PointCompression,
+{
+    const COMPRESS_POINTS: bool = C::COMPRESS_POINTS;
+}
+
+#[cfg(feature = "ecdsa")]
+impl<C> DigestPrimitive for Instrumented<C>
+where
+    C: PrimeCurveParams<Uint = ReprUint, FieldBytesSize = ReprSizeTypenum> + DigestPrimitive,
+{
+    type Digest = C::Digest;
+}
+
+/// A point of an [`Instrumented`] curve in affine coordinates.
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct InstrumentedAffinePoint<C: PrimeCurveParams>(AffinePoint<C>);
+
+impl<C> AffineCoordinates for InstrumentedAffinePoint<C>
+where
+    C: PrimeCurveParams<Uint = ReprUint, FieldBytesSize = ReprSizeTypenum>,
+{
+    type FieldRepr = FieldBytes<Instrumented<C>>;
+
+    fn x(&self) -> Self::FieldRepr {
+        self.0.x()
+    }
+
+    fn y_is_odd(&self) -> Choice {
+        self.0.y_is_odd()
+    }
+}
+
+impl<C: PrimeCurveParams> ConditionallySelectable for InstrumentedAffinePoint<C> {
+    fn conditional_select(lhs: &Self, rhs: &Self, choice: Choice) -> Self {
+        Self(AffinePoint::<C>::conditional_select(&lhs.0, &rhs.0, choice))
+    }
+}
+
+impl<C: PrimeCurveParams> ConstantTimeEq for InstrumentedAffinePoint<C> {
+    fn ct_eq(&self, rhs: &Self) -> Choice {
+        self.0.ct_eq(&rhs.0)
+    }
+}
+
+impl<C: PrimeCurveParams> DefaultIsZeroes for InstrumentedAffinePoint<C> {}
+
+impl<C> DecompressPoint<Instrumented<C>> for InstrumentedAffinePoint<C>
+where
+    C: PrimeCurveParams<Uint = ReprUint, FieldBytesSize = ReprSizeTypenum>,
+{
+    fn decompress(x: &FieldBytes<Instrumented<C>>, y_is_odd: Choice) -> CtOption<Self> {
+        AffinePoint::<C>::decompress(x, y_is_odd).map(Self)
+    }
+}
+
+impl<C> FromEncodedPoint<Instrumented<C>> for InstrumentedAffinePoint<C>
+where
+    C: PrimeCurveParams<Uint = ReprUint, FieldBytesSize = ReprSizeTypenum>,
+{
+    fn from_encoded_point(point: &EncodedPoint<Instrumented<C>>) -> CtOption<Self> {
+        AffinePoint::<C>::from_encoded_point(point).map(Self)
+    }
+}
+
+impl<C> ToEncodedPoint<Instrumented<C>> for InstrumentedAffinePoint<C>
+where
+    C: PrimeCurveParams<Uint = ReprUint, FieldBytesSize = ReprSizeTypenum>,
+{
+    fn to_encoded_point(&self, compress: bool) -> EncodedPoint<Instrumented<C>> {
+        self.0.to_encoded_point(compress)
+    }
+}
+
+impl<C: PrimeCurveParams> From<InstrumentedProjectivePoint<C>> for InstrumentedAffinePoint<C> {
+    fn from(source: InstrumentedProjectivePoint<C>) -> Self {
+        Self(source.0.into())
+    }
+}
+
+/// A point of an [`Instrumented`] curve in projective coordinates.
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct InstrumentedProjectivePoint<C: PrimeCurveParams>(ProjectivePoint<C>);
+
+impl<C: PrimeCurveParams> InstrumentedProjectivePoint<C> {
+    const IDENTITY: Self = Self(ProjectivePoint::<C>::IDENTITY);
+    const GENERATOR: Self = Self(ProjectivePoint::<C>::GENERATOR);
+}
+
+impl<C: PrimeCurveParams> From<InstrumentedAffinePoint<C>> for InstrumentedProjectivePoint<C> {
+    fn from(source: InstrumentedAffinePoint<C>) -> Self {
+        Self(source.0.into())
+    }
+}
+
+impl<C: PrimeCurveParams> ConditionallySelectable for InstrumentedProjectivePoint<C> {
+    fn conditional_select(lhs: &Self, rhs: &Self, choice: Choice) -> Self {
+        Self(ProjectivePoint::<C>::conditional_select(
+            &lhs.0, &rhs.0, choice,
+        ))
+    }
+}
+
+impl<C: PrimeCurveParams> ConstantTimeEq for InstrumentedProjectivePoint<C> {
+    fn ct_eq(&self, rhs: &Self) -> Choice {
+        self.0.ct_eq(&rhs.0)
+    }
+}
+
+impl<C: PrimeCurveParams> DefaultIsZeroes for InstrumentedProjectivePoint<C> {}
+
+impl<'a, C: PrimeCurveParams> AddAssign<&'a Self> for InstrumentedProjectivePoint<C> {
+    fn add_assign(&mut self, rhs: &'a Self) {
+        record(Op::PointAdd);
+        self.0 += rhs.0
+    }
+}
+
+impl<'a, C: PrimeCurveParams> SubAssign<&'a Self> for InstrumentedProjectivePoint<C> {
+    fn sub_assign(&mut self, rhs: &'a Self) {
+        record(Op::PointAdd);
+        self.0 -= rhs.0
+    }
+}
+
+impl<'a, C: PrimeCurveParams> AddAssign<&'a InstrumentedAffinePoint<C>>
+    for InstrumentedProjectivePoint<C>
+{
+    fn add_assign(&mut self, rhs: &'a InstrumentedAffinePoint<C>) {
+        record(Op::PointAdd);
+        self.0 += rhs.0
+    }
+}
+
+impl<'a, C: PrimeCurveParams> SubAssign<&'a InstrumentedAffinePoint<C>>
+    for InstrumentedProjectivePoint<C>
+{
+    fn sub_assign(&mut self, rhs: &'a InstrumentedAffinePoint<C>) {
+        record(Op::PointAdd);
+        self.0 -= rhs.0
+    }
+}
+
+/// Implements a binary operation `Self x Rhs -> Self` for owned and borrowed `Rhs`,
+/// given the assigning operation for borrowed `Rhs`.
+macro_rules! impl_binary_op {
+    ($rhs:ty, $trait:ident, $method:ident, $assign_trait:ident, $assign_method:ident) => {
+        impl<C: PrimeCurveParams> $assign_trait<$rhs> for InstrumentedProjectivePoint<C> {
+            fn $assign_method(&mut self, rhs: $rhs) {
+                self.$assign_method(&rhs)
+            }
+        }
+
+        impl<C: PrimeCurveParams> $trait<$rhs> for InstrumentedProjectivePoint<C> {
+            type Output = Self;
+            fn $method(mut self, rhs: $rhs) -> Self::Output {
+                self.$assign_method(&rhs);
+                self
+            }
+        }
+
+        impl<'a, C: PrimeCurveParams> $trait<&'a $rhs> for InstrumentedProjectivePoint<C> {
+            type Output = Self;
+            fn $method(mut self, rhs: &'a $rhs) -> Self::Output {
+                self.$assign_method(rhs);
+                self
+            }
+        }
+    };
+}
+
+impl_binary_op!(
+    InstrumentedProjectivePoint<C>,
+    Add,
+    add,
+    AddAssign,
+    add_assign
+);
+impl_binary_op!(
+    InstrumentedProjectivePoint<C>,
+    Sub,
+    sub,
+    SubAssign,
+    sub_assign
+);
+impl_binary_op!(InstrumentedAffinePoint<C>, Add, add, AddAssign, add_assign);
+impl_binary_op!(InstrumentedAffinePoint<C>, Sub, sub, SubAssign, sub_assign);
+
+impl<C: PrimeCurveParams> Neg for InstrumentedProjectivePoint<C> {
+    type Output = Self;
+    fn neg(self) -> Self::Output {
+        Self(-self.0)
+    }
+}
+
+impl<C: PrimeCurveParams> Sum for InstrumentedProjectivePoint<C> {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::IDENTITY, Add::add)
+    }
+}
+
+impl<'a, C: PrimeCurveParams> Sum<&'a Self> for InstrumentedProjectivePoint<C> {
+    fn sum<I: Iterator<Item = &'a Self>>(iter: I) -> Self {
+        iter.fold(Self::IDENTITY, Add::add)
+    }
+}
+
+/// Implements the traits that depend on the scalar of the wrapped curve
+/// (they would conflict with the generic ones otherwise).
+macro_rules! impl_instrumented {
+    ($curve:ident) => {
+        impl CurveArithmetic for Instrumented<$curve> {
+            type Scalar = <$curve as CurveArithmetic>::Scalar;
+            type AffinePoint = InstrumentedAffinePoint<$curve>;
+            type ProjectivePoint = InstrumentedProjectivePoint<$curve>;
+        }
+
+        #[cfg(feature = "ecdsa")]
+        impl VerifyPrimitive<Instrumented<$curve>> for InstrumentedAffinePoint<$curve> {}
+
+        impl<'a> MulAssign<&'a <$curve as CurveArithmetic>::Scalar>
+            for InstrumentedProjectivePoint<$curve>
+        {
+            fn mul_assign(&mut self, rhs: &'a <$curve as CurveArithmetic>::Scalar) {
+                record(Op::ScalarMul);
+                self.0 *= rhs
+            }
+        }
+
+        impl MulAssign<<$curve as CurveArithmetic>::Scalar>
+            for InstrumentedProjectivePoint<$curve>
+        {
+            fn mul_assign(&mut self, rhs: <$curve as CurveArithmetic>::Scalar) {
+                *self *= &rhs
+            }
+        }
+
+        impl Mul<<$curve as CurveArithmetic>::Scalar> for InstrumentedProjectivePoint<$curve> {
+            type Output = Self;
+            fn mul(mut self, rhs: <$curve as CurveArithmetic>::Scalar) -> Self::Output {
+                self *= &rhs;
+                self
+            }
+        }
+
+        impl<'a> Mul<&'a <$curve as CurveArithmetic>::Scalar>
+            for InstrumentedProjectivePoint<$curve>
+        {
+            type Output = Self;
+            fn mul(mut self, rhs: &'a <$curve as CurveArithmetic>::Scalar) -> Self::Output {
+                self *= rhs;
+                self
+            }
+        }
+
+        impl Group for InstrumentedProjectivePoint<$curve> {
+            type Scalar = <$curve as CurveArithmetic>::Scalar;
+
+            fn random(rng: impl RngCore) -> Self {
+                Self(ProjectivePoint::<$curve>::random(rng))
+            }
+
+            fn identity() -> Self {
+                Self::IDENTITY
+            }
+
+            fn generator() -> Self {
+                Self::GENERATOR
+            }
+
+            fn is_identity(&self) -> Choice {
+                self.0.is_identity()
+            }
+
+            fn double(&self) -> Self {
+                record(Op::PointDouble);
+                Self(self.0.double())
+            }
+        }
+
+        impl group::Curve for InstrumentedProjectivePoint<$curve> {
+            type AffineRepr = InstrumentedAffinePoint<$curve>;
+
+            fn to_affine(&self) -> Self::AffineRepr {
+                InstrumentedAffinePoint(self.0.to_affine())
+            }
+        }
+
+        impl LinearCombination for InstrumentedProjectivePoint<$curve> {}
+
+        impl MulByGenerator for InstrumentedProjectivePoint<$curve> {}
+    };
+}
+
+impl_instrumented!(TinyCurve16);
+impl_instrumented!(TinyCurve32);
+impl_instrumented!(TinyCurve64);
+
+#[cfg(test)]
+mod tests {
+    use primeorder::elliptic_curve::{
+        group::{Curve as _, Group},
+        ops::MulByGenerator,
+        CurveArithmetic, Field, ProjectivePoint,
+    };
+    use rand_core::OsRng;
+
+    use super::{measure, record_composite, Instrumented, Op, OpCounts};
+    use crate::{TinyCurve16, TinyCurve32, TinyCurve64};
+
+    #[test]
+    fn field_ops() {
+        type F = <TinyCurve64 as CurveArithmetic>::Scalar;
+        let x = F::random(&mut OsRng);
+        let y = F::random(&mut OsRng);
+
+        let (_, counts) = measure(|| {
+            let z = x * y + x.square() - y;
+            let _ = z.invert();
+            let _ = z.sqrt();
+        });
+        assert_eq!(
+            counts,
+            OpCounts {
+                field_add: 2,
+                field_mul: 1,
+                field_square: 1,
+                field_invert: 1,
+                field_sqrt: 1,
+                ..Default::default()
+            }
+        );
+    }
+
+    #[test]
+    fn nested() {
+        type F = <TinyCurve16 as CurveArithmetic>::Scalar;
+        let x = F::random(&mut OsRng);
+
+        let ((_, inner), outer) = measure(|| {
+            let y = x * x;
+            measure(|| y * x)
+        });
+        assert_eq!(inner.field_mul, 1);
+        assert_eq!(outer.field_mul, 2);
+    }
+
+    #[test]
+    fn panic_in_composite() {
+        type F = <TinyCurve16 as CurveArithmetic>::Scalar;
+        let x = F::random(&mut OsRng);
+
+        let result = std::panic::catch_unwind(|| {
+            record_composite(Op::ScalarMul, || -> () {
+                panic!("composite operation failed")
+            })
+        });
+        assert!(result.is_err());
+
+        // The counters are not left paused
+        let (_, counts) = measure(|| x * x);
+        assert_eq!(counts.field_mul, 1);
+    }
+
+    fn check_point_ops<C>()
+    where
+        C: CurveArithmetic,
+        Instrumented<C>: CurveArithmetic<Scalar = C::Scalar>,
+    {
+        let k = C::Scalar::random(&mut OsRng);
+
+        let (_, counts) = measure(|| {
+            let p = ProjectivePoint::<Instrumented<C>>::mul_by_generator(&k);
+            let q = p.double() + p;
+            q.to_affine()
+        });
+        assert_eq!(counts.scalar_mul, 1);
+        assert_eq!(counts.point_add, 1);
+        assert_eq!(counts.point_double, 1);
+        // The final normalization
+        assert_eq!(counts.field_invert, 1);
+    }
+
+    #[test]
+    fn point_ops() {
+        check_point_ops::<TinyCurve16>();
+        check_point_ops::<TinyCurve32>();
+        check_point_ops::<TinyCurve64>();
+    }
+}
+
+#[cfg(all(test, feature = "ecdsa"))]
+mod tests_ecdsa {
+    use ecdsa::{
+        signature::hazmat::{PrehashSigner, PrehashVerifier},
+        Signature, SigningKey,
+    };
+    use rand_core::OsRng;
+
+    use super::{measure, Instrumented};
+    use crate::TinyCurve32;
+
+    #[test]
+    fn verification_cost() {
+        let prehash = b"123456781234567812345678";
+        let sk = SigningKey::<Instrumented<TinyCurve32>>::random(&mut OsRng);
+        let vk = *sk.verifying_key();
+        let signature: Signature<_> = sk.sign_prehash(prehash).unwrap();
+
+        let (result, counts) = measure(|| vk.verify_prehash(prehash, &signature));
+        result.unwrap();
+
+        assert_eq!(counts.scalar_mul, 2);
+        assert_eq!(counts.point_add, 1);
+        // The inversion of `s`, and the normalization of the result.
+        assert_eq!(counts.field_invert, 2);
+    }
+}
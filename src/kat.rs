@@ -0,0 +1,198 @@
+//! Known-answer tests pinning down the exact outputs for the curves
+//! (generated by an independent implementation in `test-vectors/kat`).
+
+use serde_json::Value;
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{byte:02x}")).collect()
+}
+
+fn from_hex(value: &Value) -> Vec<u8> {
+    let s = value.as_str().unwrap();
+    (0..s.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&s[i..i + 2], 16).unwrap())
+        .collect()
+}
+
+macro_rules! kat_tests {
+    ($module:ident, $curve:ident, $name:literal) => {
+        mod $module {
+            use serde_json::Value;
+
+            use super::{from_hex, to_hex};
+            use crate::$curve;
+
+            fn vectors() -> Value {
+                serde_json::from_str(include_str!(concat!(
+                    "../test-vectors/kat/",
+                    $name,
+                    ".json"
+                )))
+                .unwrap()
+            }
+
+            #[cfg(feature = "pkcs8")]
+            #[test]
+            fn encodings() {
+                use primeorder::elliptic_curve::{
+                    pkcs8::{DecodePrivateKey, DecodePublicKey, EncodePrivateKey, EncodePublicKey},
+                    sec1::ToEncodedPoint,
+                    PublicKey, SecretKey,
+                };
+
+                for vector in vectors()["encodings"].as_array().unwrap() {
+                    let sk =
+                        SecretKey::<$curve>::from_slice(&from_hex(&vector["secret_key"])).unwrap();
+                    let pk = sk.public_key();
+                    assert_eq!(to_hex(&sk.to_bytes()), vector["secret_key"]);
+                    assert_eq!(
+                        to_hex(pk.to_encoded_point(true).as_bytes()),
+                        vector["public_key_compressed"]
+                    );
+                    assert_eq!(
+                        to_hex(pk.to_encoded_point(false).as_bytes()),
+                        vector["public_key_uncompressed"]
+                    );
+                    assert_eq!(to_hex(&sk.to_sec1_der().unwrap()), vector["sec1_der"]);
+                    assert_eq!(
+                        to_hex(sk.to_pkcs8_der().unwrap().as_bytes()),
+                        vector["pkcs8_der"]
+                    );
+                    assert_eq!(
+                        to_hex(pk.to_public_key_der().unwrap().as_bytes()),
+                        vector["spki_der"]
+                    );
+
+                    // Decoding
+                    assert_eq!(
+                        PublicKey::<$curve>::from_sec1_bytes(&from_hex(
+                            &vector["public_key_compressed"]
+                        ))
+                        .unwrap(),
+                        pk
+                    );
+                    assert_eq!(
+                        PublicKey::<$curve>::from_sec1_bytes(&from_hex(
+                            &vector["public_key_uncompressed"]
+                        ))
+                        .unwrap(),
+                        pk
+                    );
+                    assert_eq!(
+                        SecretKey::<$curve>::from_sec1_der(&from_hex(&vector["sec1_der"])).unwrap(),
+                        sk
+                    );
+                    assert_eq!(
+                        SecretKey::<$curve>::from_pkcs8_der(&from_hex(&vector["pkcs8_der"]))
+                            .unwrap(),
+                        sk
+                    );
+                    assert_eq!(
+                        PublicKey::<$curve>::from_public_key_der(&from_hex(&vector["spki_der"]))
+                            .unwrap(),
+                        pk
+                    );
+                }
+            }
+
+            #[cfg(feature = "ecdsa")]
+            #[test]
+            fn ecdsa_rfc6979() {
+                use ecdsa::{
+                    signature::{Signer, Verifier},
+                    Signature, SigningKey, VerifyingKey,
+                };
+                use primeorder::elliptic_curve::PrimeField;
+
+                for vector in vectors()["ecdsa_rfc6979"].as_array().unwrap() {
+                    let sk =
+                        SigningKey::<$curve>::from_slice(&from_hex(&vector["secret_key"])).unwrap();
+                    let msg = vector["msg"].as_str().unwrap().as_bytes();
+
+                    let signature: Signature<$curve> = sk.sign(msg);
+                    assert_eq!(to_hex(&signature.r().to_repr()), vector["r"]);
+                    assert_eq!(to_hex(&signature.s().to_repr()), vector["s"]);
+                    assert_eq!(to_hex(&signature.to_bytes()), vector["signature"]);
+                    assert_eq!(
+                        to_hex(signature.to_der().as_bytes()),
+                        vector["signature_der"]
+                    );
+
+                    let (recoverable_signature, recovery_id) = sk.sign_recoverable(msg).unwrap();
+                    assert_eq!(recoverable_signature, signature);
+                    assert_eq!(
+                        u64::from(recovery_id.to_byte()),
+                        vector["recovery_id"].as_u64().unwrap()
+                    );
+
+                    let vk = VerifyingKey::from(&sk);
+                    assert!(vk.verify(msg, &signature).is_ok());
+                    assert_eq!(
+                        VerifyingKey::recover_from_msg(msg, &signature, recovery_id).unwrap(),
+                        vk
+                    );
+                }
+            }
+
+            #[cfg(feature = "bip32")]
+            #[test]
+            fn bip32() {
+                use bip32::{ChildNumber, PrivateKey as _, PublicKey as _};
+
+                use crate::{PrivateKeyBip32, PublicKeyBip32};
+
+                let vectors = vectors()["bip32"].clone();
+                let mut sk = PrivateKeyBip32::<$curve>::from_bytes(
+                    &from_hex(&vectors["private_key"]).try_into().unwrap(),
+                )
+                .unwrap();
+                let mut pk = sk.public_key();
+                let mut chain_code: [u8; 32] = from_hex(&vectors["chain_code"]).try_into().unwrap();
+                assert_eq!(to_hex(&pk.to_bytes()), vectors["public_key"]);
+
+                for child_number in vectors["invalid_child_numbers"].as_array().unwrap() {
+                    let child_number =
+                        ChildNumber(child_number.as_u64().unwrap().try_into().unwrap());
+                    let (tweak, _chain_code) = sk.derive_tweak(&chain_code, child_number).unwrap();
+                    assert_eq!(sk.derive_child(tweak), Err(bip32::Error::Crypto));
+                    if !child_number.is_hardened() {
+                        assert_eq!(pk.derive_child(tweak), Err(bip32::Error::Crypto));
+                    }
+                }
+
+                for step in vectors["path"].as_array().unwrap() {
+                    let child_number =
+                        ChildNumber(step["child_number"].as_u64().unwrap().try_into().unwrap());
+                    let (tweak, child_chain_code) =
+                        sk.derive_tweak(&chain_code, child_number).unwrap();
+                    let child_sk = sk.derive_child(tweak).unwrap();
+
+                    if !child_number.is_hardened() {
+                        let (public_tweak, public_chain_code) =
+                            pk.derive_tweak(&chain_code, child_number).unwrap();
+                        assert_eq!(public_tweak, tweak);
+                        assert_eq!(public_chain_code, child_chain_code);
+                        assert_eq!(pk.derive_child(tweak).unwrap(), child_sk.public_key());
+                    }
+
+                    sk = child_sk;
+                    pk = sk.public_key();
+                    chain_code = child_chain_code;
+
+                    assert_eq!(to_hex(&sk.to_bytes()), step["private_key"]);
+                    assert_eq!(to_hex(&pk.to_bytes()), step["public_key"]);
+                    assert_eq!(to_hex(&chain_code), step["chain_code"]);
+                    assert_eq!(
+                        PublicKeyBip32::<$curve>::from_bytes(pk.to_bytes()).unwrap(),
+                        pk
+                    );
+                }
+            }
+        }
+    };
+}
+
+kat_tests!(tiny_curve_16, TinyCurve16, "TinyCurve16");
+kat_tests!(tiny_curve_32, TinyCurve32, "TinyCurve32");
+kat_tests!(tiny_curve_64, TinyCurve64, "TinyCurve64");
@@ -17,32 +17,134 @@
 ## Features
 
 `serde`: `serde` support for [`elliptic_curve::PublicKey`]
-parametrized by the curves from this crate.
+parametrized by the curves from this crate, and for the types from this crate
+(plus the points, keys, and signatures, via [`serde_compact`]) using [`CompactEncoding`].
 
 `ecdsa`: [`ecdsa`](`::ecdsa`) support for [`ecdsa::SigningKey`] and [`ecdsa::VerifyingKey`]
-parametrized by the curves from this crate.
+parametrized by the curves from this crate, using [`TinyHash`] over SHA-256 as the digest
+([`DigestPrimitive::Digest`](`::ecdsa::hazmat::DigestPrimitive::Digest`)).
+Other digests can be used with [`sign_with_digest`].
+All the public keys a signature can be recovered to are returned by [`candidate_public_keys`].
+
+**Breaking change:** [`TinyHash`] keeps the leftmost bytes of the wrapped digest
+(the FIPS 186-5 truncation), while the digest used before kept the bytes `[L - BYTES, L)`
+of SHA-256, where `L` is the size of the field representation.
+All the ECDSA signatures and RFC 6979 nonces changed with it;
+the old ones can be reproduced with [`TinyHashLegacy`] and [`sign_with_digest`].
 
 `pkcs8`: [`elliptic_curve::pkcs8`] support for [`elliptic_curve::SecretKey`]
 and [`elliptic_curve::PublicKey`] parametrized by the curves from this crate.
 
 `bip32`: [`bip32`](`::bip32`) support via newtypes [`PrivateKeyBip32`] and [`PublicKeyBip32`].
+The child key tweak is taken from the leftmost bytes of `IL` (as many as there are in the order);
+an out-of-range tweak or an invalid child key results in an error,
+and the next child number should be used instead, as in BIP32.
+
+With `ecdsa` or `bip32` enabled, [`witness`] provides searches for (and cached examples of)
+rare events like ECDSA signatures with `R.x >= n`.
+
+`montgomery16`, `montgomery32`, `montgomery64`: keep the field elements
+of [`TinyCurve16`], [`TinyCurve32`] or [`TinyCurve64`] respectively in the Montgomery form internally
+(the results of all operations are the same).
+In the crate's benchmarks only `montgomery32` makes the scalar multiplication faster
+(by about 15%); for the other two curves the difference is within the noise or negative,
+so they are mostly useful for testing the code against a different representation.
+`montgomery` enables all three.
+
+`bits`: [`ff::PrimeFieldBits`](elliptic_curve::ff::PrimeFieldBits) for the field elements.
+
+`hash2curve`: hashing to the curves ([RFC 9380](https://www.rfc-editor.org/rfc/rfc9380))
+via [`elliptic_curve::hash2curve::GroupDigest`] and [`HashToCurveSuite`].
+
+`alloc`: element-wise arithmetic over vectors of the 16- and 32-bit field elements with [`FieldVec`].
+
+`wycheproof`: test vector generation in the Wycheproof format with [`wycheproof`],
+and the `tiny-curve-wycheproof` binary writing them to files.
+
+`ct-audit`: the variable-time primitives report to [`audit`], which can record them or panic,
+so the secrets passing through any code (not only [`SecretScalar`]) can be checked (requires `std`).
+
+`instrument`: operation counting via [`measure`] and [`Instrumented`] (requires `std`).
+
+`test-utils`: curves for testing the code generic over curves, not to be used otherwise:
+[`FaultyCurve16`], a copy of [`TinyCurve16`] with defects that can be switched on via [`Faults`],
+and [`TransparentCurve`], whose points carry their discrete logarithms.
 */
 
+#[cfg(feature = "alloc")]
+extern crate alloc;
+#[cfg(any(feature = "instrument", feature = "ct-audit"))]
+extern crate std;
+
+mod barrett;
+mod batch;
+mod compact;
 mod curve16;
 mod curve32;
 mod curve64;
+#[cfg(feature = "test-utils")]
+mod faulty;
+#[cfg(feature = "alloc")]
+mod field_vec;
+#[cfg(feature = "ecdsa")]
 mod hash;
+#[cfg(feature = "hash2curve")]
+mod hash2curve;
+#[cfg(all(test, any(feature = "ecdsa", feature = "pkcs8", feature = "bip32")))]
+mod kat;
+mod montgomery;
+mod msm;
+mod point;
+mod precomputed;
 mod prime_field;
 mod primitives;
 mod reciprocal;
+#[cfg(feature = "ecdsa")]
+mod recovery;
+mod secret;
+#[cfg(feature = "serde")]
+pub mod serde_compact;
 mod traits;
+#[cfg(feature = "test-utils")]
+mod transparent;
+#[cfg(any(feature = "ecdsa", feature = "bip32"))]
+pub mod witness;
+#[cfg(feature = "wycheproof")]
+pub mod wycheproof;
 
 #[cfg(feature = "bip32")]
 mod bip32;
+#[cfg(feature = "instrument")]
+mod instrumented;
 
-pub use curve16::TinyCurve16;
-pub use curve32::TinyCurve32;
-pub use curve64::TinyCurve64;
+pub use batch::batch_normalize;
+pub use compact::CompactEncoding;
+pub use curve16::{FieldElement16, Point16, Scalar16, TinyCurve16};
+pub use curve32::{FieldElement32, Point32, Scalar32, TinyCurve32};
+pub use curve64::{FieldElement64, Point64, Scalar64, TinyCurve64};
+pub use msm::{msm, msm_vartime};
+pub use point::{Point, PointU64};
+pub use precomputed::{PrecomputedBase, PrecomputedGenerator};
+pub use prime_field::{FieldElement, ParseError};
+pub use secret::{SecretScalar, VartimeInfo, VartimeOps};
 
 #[cfg(feature = "bip32")]
 pub use bip32::{PrivateKeyBip32, PublicKeyBip32};
+#[cfg(feature = "test-utils")]
+pub use faulty::{Faults, FaultyAffinePoint, FaultyCurve16, FaultyProjectivePoint, FaultyScalar};
+#[cfg(feature = "alloc")]
+pub use field_vec::FieldVec;
+#[cfg(feature = "ecdsa")]
+pub use hash::{sign_with_digest, TinyHash, TinyHashLegacy};
+#[cfg(feature = "hash2curve")]
+pub use hash2curve::HashToCurveSuite;
+#[cfg(feature = "instrument")]
+pub use instrumented::{
+    measure, Instrumented, InstrumentedAffinePoint, InstrumentedProjectivePoint, OpCounts,
+};
+#[cfg(feature = "ecdsa")]
+pub use recovery::candidate_public_keys;
+#[cfg(feature = "ct-audit")]
+pub use secret::{audit, AuditMode};
+#[cfg(feature = "test-utils")]
+pub use transparent::{TransparentCurve, TransparentPoint};
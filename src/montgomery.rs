@@ -0,0 +1,133 @@
+//! Montgomery multiplication for moduli that fit into 64 bits,
+//! with `R = 2^bits` where `bits` is the size of the integer type the field is based on.
+//!
+//! The calculations do not branch on the values; for `R <= 2^32` they fit into `u64`.
+
+/// Precomputed constants for the Montgomery form modulo `modulus`.
+#[derive(Copy, Clone, Debug)]
+pub struct Montgomery {
+    modulus: u64,
+    bits: u32,
+    /// `R - 1`
+    mask: u128,
+    /// `-modulus^(-1) mod R`
+    m_prime: u64,
+    /// `R^2 mod modulus`
+    r2: u64,
+}
+
+impl Montgomery {
+    /// Pre-calculates the constants for an odd modulus and `R = 2^bits`.
+    pub const fn new(modulus: u64, bits: u32) -> Self {
+        debug_assert!(modulus & 1 == 1);
+        debug_assert!(bits <= u64::BITS);
+
+        // Newton's iteration: for odd `m`, `m * m = 1 mod 8`,
+        // and every step doubles the number of correct bits.
+        let mut inv = modulus;
+        let mut i = 0;
+        while i < 5 {
+            inv = inv.wrapping_mul(2u64.wrapping_sub(modulus.wrapping_mul(inv)));
+            i += 1;
+        }
+
+        let mask = (1u128 << bits) - 1;
+        let r = (1u128 << bits) % (modulus as u128);
+        Self {
+            modulus,
+            bits,
+            mask,
+            m_prime: (inv.wrapping_neg() as u128 & mask) as u64,
+            r2: ((r * r) % (modulus as u128)) as u64,
+        }
+    }
+
+    /// Calculates `x * R^(-1) mod modulus` for `x < modulus * R`.
+    #[inline(always)]
+    pub const fn reduce(&self, x: u128) -> u64 {
+        if self.bits == u64::BITS {
+            self.reduce_wide(x)
+        } else {
+            self.reduce_narrow(x as u64)
+        }
+    }
+
+    /// [`Self::reduce`] for `R <= 2^32`, where `x` and the intermediate products fit into `u64`.
+    #[inline(always)]
+    const fn reduce_narrow(&self, x: u64) -> u64 {
+        let mask = self.mask as u64;
+        let x_lo = x & mask;
+        let m = x_lo.wrapping_mul(self.m_prime) & mask;
+        let mm = m * self.modulus;
+        // `x + m * modulus` is divisible by `R`, so the sum of the low parts is either `0`
+        // (if `x_lo == 0`) or `R`; this gives the carry without having to calculate the full sum
+        // (which can overflow `u64`).
+        let carry = (x_lo | x_lo.wrapping_neg()) >> (u64::BITS - 1);
+        let t = (x >> self.bits) + (mm >> self.bits) + carry;
+        // `t < 2 * modulus`, so one conditional subtraction suffices.
+        let (t_reduced, borrow) = t.overflowing_sub(self.modulus);
+        let borrow_mask = (borrow as u64).wrapping_neg();
+        (t_reduced & !borrow_mask) | (t & borrow_mask)
+    }
+
+    /// [`Self::reduce`] for `R = 2^64`.
+    #[inline(always)]
+    const fn reduce_wide(&self, x: u128) -> u64 {
+        let x_lo = x as u64;
+        let m = x_lo.wrapping_mul(self.m_prime);
+        let mm = m as u128 * self.modulus as u128;
+        // Same as in `reduce_narrow()`.
+        let carry = ((x_lo | x_lo.wrapping_neg()) >> (u64::BITS - 1)) as u128;
+        let t = (x >> u64::BITS) + (mm >> u64::BITS) + carry;
+        let (t_reduced, borrow) = t.overflowing_sub(self.modulus as u128);
+        let borrow_mask = (borrow as u128).wrapping_neg();
+        ((t_reduced & !borrow_mask) | (t & borrow_mask)) as u64
+    }
+
+    /// Returns `a * b * R^(-1) mod modulus` for `a, b < modulus`.
+    #[inline(always)]
+    pub const fn mul(&self, a: u64, b: u64) -> u64 {
+        self.reduce(a as u128 * b as u128)
+    }
+
+    /// Converts `x < modulus` to the Montgomery form `x * R mod modulus`.
+    #[inline(always)]
+    pub const fn encode(&self, x: u64) -> u64 {
+        self.mul(x, self.r2)
+    }
+
+    /// Converts `x` from the Montgomery form.
+    #[cfg(any(
+        test,
+        feature = "montgomery16",
+        feature = "montgomery32",
+        feature = "montgomery64"
+    ))]
+    #[inline(always)]
+    pub const fn decode(&self, x: u64) -> u64 {
+        self.reduce(x as u128)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Montgomery;
+    use proptest::prelude::*;
+
+    const MODULI: [(u64, u32); 3] = [(0xffa7, 16), (0xffffff67, 32), (0xfffffffffffffc7f, 64)];
+
+    proptest! {
+        #[test]
+        fn mul(x in any::<u64>(), y in any::<u64>()) {
+            for (m, bits) in MODULI {
+                let mont = Montgomery::new(m, bits);
+                let (x, y) = (x % m, y % m);
+                let expected = ((x as u128 * y as u128) % m as u128) as u64;
+                let test = mont.decode(
+                    mont.mul(mont.encode(x), mont.encode(y))
+                );
+                assert_eq!(test, expected);
+            }
+        }
+    }
+}
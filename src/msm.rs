@@ -0,0 +1,192 @@
+//! Multi-scalar multiplication.
+//!
+//! `LinearCombinationExt` cannot be implemented for `primeorder`'s projective points
+//! outside of `primeorder`, so these are provided as standalone functions
+//! taking the same arguments as `LinearCombinationExt::lincomb_ext()`.
+
+use primeorder::{
+    elliptic_curve::{
+        point::Double,
+        subtle::{ConditionallySelectable, ConstantTimeEq},
+        CurveArithmetic, PrimeField,
+    },
+    PrimeCurveParams, ProjectivePoint,
+};
+
+// The window size in bits in the constant-time multiplication.
+const CT_WINDOW: usize = 4;
+
+// The number of points whose multiples are precomputed at the same time in the constant-time
+// multiplication. The tables are stored on stack, so this is a compromise
+// between the number of shared doublings and the stack usage.
+const CT_CHUNK: usize = 16;
+
+// The maximum window size in bits in the variable-time multiplication
+// (the buckets are stored on stack).
+const MAX_WINDOW: usize = 8;
+
+/// Returns `width` bits of the big-endian `repr` starting from the bit `start`
+/// (counting from the least significant one).
+pub(crate) fn digit(repr: &[u8], start: usize, width: usize) -> usize {
+    let mut result = 0;
+    for i in 0..width {
+        let bit = start + i;
+        let byte = repr[repr.len() - 1 - bit / 8];
+        result |= (((byte >> (bit % 8)) & 1) as usize) << i;
+    }
+    result
+}
+
+/// Calculates `x1 * k1 + ... + xn * kn` in time that does not depend on the scalars.
+///
+/// Uses windowed multiplication with the doublings shared between groups of points.
+pub fn msm<C>(points_and_scalars: &[(ProjectivePoint<C>, C::Scalar)]) -> ProjectivePoint<C>
+where
+    C: PrimeCurveParams,
+    ProjectivePoint<C>: Double,
+{
+    points_and_scalars
+        .chunks(CT_CHUNK)
+        .fold(ProjectivePoint::<C>::IDENTITY, |acc, chunk| {
+            acc + msm_chunk(chunk)
+        })
+}
+
+fn msm_chunk<C>(points_and_scalars: &[(ProjectivePoint<C>, C::Scalar)]) -> ProjectivePoint<C>
+where
+    C: PrimeCurveParams,
+    ProjectivePoint<C>: Double,
+{
+    debug_assert!(points_and_scalars.len() <= CT_CHUNK);
+
+    // `tables[i][j] = j * points[i]`
+    let mut tables = [[ProjectivePoint::<C>::IDENTITY; 1 << CT_WINDOW]; CT_CHUNK];
+    for (table, (point, _scalar)) in tables.iter_mut().zip(points_and_scalars) {
+        for j in 1..table.len() {
+            table[j] = table[j - 1] + point;
+        }
+    }
+
+    let windows = (<C as CurveArithmetic>::Scalar::NUM_BITS as usize).div_ceil(CT_WINDOW);
+    let mut acc = ProjectivePoint::<C>::IDENTITY;
+    for window in (0..windows).rev() {
+        for _ in 0..CT_WINDOW {
+            acc = acc.double();
+        }
+
+        for (table, (_point, scalar)) in tables.iter().zip(points_and_scalars) {
+            let digit = digit(&scalar.to_repr(), window * CT_WINDOW, CT_WINDOW) as u8;
+            let mut multiple = ProjectivePoint::<C>::IDENTITY;
+            for (j, entry) in table.iter().enumerate() {
+                multiple.conditional_assign(entry, (j as u8).ct_eq(&digit));
+            }
+            acc += multiple;
+        }
+    }
+
+    acc
+}
+
+/// Calculates `x1 * k1 + ... + xn * kn` using Pippenger's (bucket) method.
+///
+/// The time depends on the scalars, so they must not be secret.
+pub fn msm_vartime<C>(points_and_scalars: &[(ProjectivePoint<C>, C::Scalar)]) -> ProjectivePoint<C>
+where
+    C: PrimeCurveParams,
+    ProjectivePoint<C>: Double,
+{
+    if points_and_scalars.is_empty() {
+        return ProjectivePoint::<C>::IDENTITY;
+    }
+
+    // The optimal window is approximately `ln(n) + 2`.
+    let window_size = (points_and_scalars.len().ilog2() as usize * 69 / 100 + 2).min(MAX_WINDOW);
+    let windows = (<C as CurveArithmetic>::Scalar::NUM_BITS as usize).div_ceil(window_size);
+
+    // The bucket for the digit `j` is `buckets[j - 1]` (the zero digit does not need one).
+    let mut all_buckets = [ProjectivePoint::<C>::IDENTITY; (1 << MAX_WINDOW) - 1];
+    let buckets = &mut all_buckets[..(1 << window_size) - 1];
+
+    let mut acc = ProjectivePoint::<C>::IDENTITY;
+    for window in (0..windows).rev() {
+        for _ in 0..window_size {
+            acc = acc.double();
+        }
+
+        buckets.fill(ProjectivePoint::<C>::IDENTITY);
+        for (point, scalar) in points_and_scalars {
+            let digit = digit(&scalar.to_repr(), window * window_size, window_size);
+            if digit != 0 {
+                buckets[digit - 1] += point;
+            }
+        }
+
+        // Calculate `sum(j * buckets[j - 1])` as the sum of the partial sums
+        // `buckets[j - 1] + ... + buckets[buckets.len() - 1]`.
+        let mut partial_sum = ProjectivePoint::<C>::IDENTITY;
+        let mut window_sum = ProjectivePoint::<C>::IDENTITY;
+        for bucket in buckets.iter().rev() {
+            partial_sum += bucket;
+            window_sum += partial_sum;
+        }
+
+        acc += window_sum;
+    }
+
+    acc
+}
+
+#[cfg(test)]
+mod tests {
+    use primeorder::{
+        elliptic_curve::{point::Double, CurveArithmetic, Field},
+        PrimeCurveParams, ProjectivePoint,
+    };
+    use rand_core::OsRng;
+
+    use super::{msm, msm_vartime};
+    use crate::{TinyCurve16, TinyCurve32, TinyCurve64};
+
+    fn check_msm<C>()
+    where
+        C: PrimeCurveParams,
+        ProjectivePoint<C>: Double,
+    {
+        type Scalar<C> = <C as CurveArithmetic>::Scalar;
+
+        for len in [0, 1, 2, 15, 16, 17, 100, 1000] {
+            let mut terms = (0..len)
+                .map(|_| {
+                    (
+                        ProjectivePoint::<C>::GENERATOR * Scalar::<C>::random(&mut OsRng),
+                        Scalar::<C>::random(&mut OsRng),
+                    )
+                })
+                .collect::<Vec<_>>();
+
+            // Some edge cases: the identity, a zero scalar, the maximum scalar, a repeated point.
+            if len >= 4 {
+                terms[0].0 = ProjectivePoint::<C>::IDENTITY;
+                terms[1].1 = Scalar::<C>::ZERO;
+                terms[2].1 = -Scalar::<C>::ONE;
+                terms[3].0 = terms[2].0;
+            }
+
+            let expected = terms
+                .iter()
+                .fold(ProjectivePoint::<C>::IDENTITY, |acc, (point, scalar)| {
+                    acc + *point * scalar
+                });
+
+            assert_eq!(msm(&terms), expected);
+            assert_eq!(msm_vartime(&terms), expected);
+        }
+    }
+
+    #[test]
+    fn against_naive() {
+        check_msm::<TinyCurve16>();
+        check_msm::<TinyCurve32>();
+        check_msm::<TinyCurve64>();
+    }
+}
@@ -0,0 +1,445 @@
+//! A point wrapper with formatting, parsing, and hashing.
+
+use core::{
+    fmt::{self, Display, LowerHex, UpperHex},
+    hash::{Hash, Hasher},
+    str::FromStr,
+};
+
+use primeorder::{
+    elliptic_curve::{
+        sec1::{EncodedPoint, FromEncodedPoint, ModulusSize, ToEncodedPoint},
+        FieldBytes, PrimeField,
+    },
+    AffinePoint, PrimeCurveParams, ProjectivePoint,
+};
+
+use crate::prime_field::ParseError;
+
+/// A newtype wrapper for a [`ProjectivePoint`] of one of the curves from this crate.
+///
+/// Formats as the affine coordinates `(x, y)` (using the given format for each of them),
+/// or as `identity` for the identity point, and is parsed from the same representation
+/// (with the coordinates either decimal or `0x`-prefixed hexadecimal).
+/// Equal points have equal hashes regardless of their projective representation.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct Point<C: PrimeCurveParams>(ProjectivePoint<C>);
+
+impl<C: PrimeCurveParams> Point<C> {
+    /// The identity point.
+    pub const IDENTITY: Self = Self(ProjectivePoint::<C>::IDENTITY);
+
+    /// The generator.
+    pub const GENERATOR: Self = Self(ProjectivePoint::<C>::GENERATOR);
+
+    /// Returns the affine coordinates of the point, or `None` for the identity.
+    pub fn coordinates(&self) -> Option<(C::FieldElement, C::FieldElement)>
+    where
+        AffinePoint<C>: ToEncodedPoint<C>,
+        C::FieldBytesSize: ModulusSize,
+    {
+        let encoded = self.0.to_affine().to_encoded_point(false);
+        let x = encoded.x()?;
+        let y = encoded.y()?;
+        let x = Option::from(C::FieldElement::from_repr(x.clone()))
+            .expect("the coordinate is a valid field element");
+        let y = Option::from(C::FieldElement::from_repr(y.clone()))
+            .expect("the coordinate is a valid field element");
+        Some((x, y))
+    }
+
+    /// Creates a point from its affine coordinates,
+    /// returning `None` if they are out of range or the point is not on the curve.
+    pub fn from_xy_u64(x: u64, y: u64) -> Option<Self>
+    where
+        AffinePoint<C>: FromEncodedPoint<C>,
+        C::FieldBytesSize: ModulusSize,
+    {
+        let x = Option::<C::FieldElement>::from(C::FieldElement::from_repr(u64_to_repr::<C>(x)))?;
+        let y = Option::<C::FieldElement>::from(C::FieldElement::from_repr(u64_to_repr::<C>(y)))?;
+        Self::from_coordinates(&x, &y)
+    }
+
+    /// Returns the affine coordinates of the point, or `None` for the identity.
+    pub fn to_xy_u64(&self) -> Option<(u64, u64)>
+    where
+        AffinePoint<C>: ToEncodedPoint<C>,
+        C::FieldBytesSize: ModulusSize,
+    {
+        self.coordinates().map(|(x, y)| {
+            (
+                repr_to_u64::<C>(&x.to_repr()),
+                repr_to_u64::<C>(&y.to_repr()),
+            )
+        })
+    }
+
+    /// Multiplies the point by an integer (reduced modulo the curve order).
+    pub fn mul_u64(&self, k: u64) -> Self {
+        Self(self.0 * C::Scalar::from(k))
+    }
+
+    fn from_coordinates(x: &C::FieldElement, y: &C::FieldElement) -> Option<Self>
+    where
+        AffinePoint<C>: FromEncodedPoint<C>,
+        C::FieldBytesSize: ModulusSize,
+    {
+        let encoded = EncodedPoint::<C>::from_affine_coordinates(&x.to_repr(), &y.to_repr(), false);
+        Option::<AffinePoint<C>>::from(AffinePoint::<C>::from_encoded_point(&encoded))
+            .map(Self::from)
+    }
+
+    fn fmt_with(
+        &self,
+        f: &mut fmt::Formatter<'_>,
+        fmt_coordinate: fn(&C::FieldElement, &mut fmt::Formatter<'_>) -> fmt::Result,
+    ) -> fmt::Result
+    where
+        AffinePoint<C>: ToEncodedPoint<C>,
+        C::FieldBytesSize: ModulusSize,
+    {
+        match self.coordinates() {
+            Some((x, y)) => {
+                f.write_str("(")?;
+                fmt_coordinate(&x, f)?;
+                f.write_str(", ")?;
+                fmt_coordinate(&y, f)?;
+                f.write_str(")")
+            }
+            None => f.write_str("identity"),
+        }
+    }
+}
+
+// The values of the curves in this crate fit in the last 8 bytes of the representation.
+
+fn u64_to_repr<C: PrimeCurveParams>(value: u64) -> FieldBytes<C> {
+    let mut repr = FieldBytes::<C>::default();
+    let repr_len = repr.len();
+    repr[repr_len - 8..].copy_from_slice(&value.to_be_bytes());
+    repr
+}
+
+fn repr_to_u64<C: PrimeCurveParams>(repr: &FieldBytes<C>) -> u64 {
+    u64::from_be_bytes(
+        repr[repr.len() - 8..]
+            .try_into()
+            .expect("slice has the correct length"),
+    )
+}
+
+impl<C: PrimeCurveParams> From<ProjectivePoint<C>> for Point<C> {
+    fn from(source: ProjectivePoint<C>) -> Self {
+        Self(source)
+    }
+}
+
+impl<C: PrimeCurveParams> From<AffinePoint<C>> for Point<C> {
+    fn from(source: AffinePoint<C>) -> Self {
+        Self(source.into())
+    }
+}
+
+/// Native integer interop for [`AffinePoint`] and [`ProjectivePoint`]
+/// of the curves from this crate (the same as the corresponding methods of [`Point`]).
+pub trait PointU64: Sized {
+    /// Creates a point from its affine coordinates,
+    /// returning `None` if they are out of range or the point is not on the curve.
+    fn from_xy_u64(x: u64, y: u64) -> Option<Self>;
+
+    /// Returns the affine coordinates of the point, or `None` for the identity.
+    fn to_xy_u64(&self) -> Option<(u64, u64)>;
+
+    /// Multiplies the point by an integer (reduced modulo the curve order).
+    fn mul_u64(&self, k: u64) -> Self;
+}
+
+impl<C> PointU64 for ProjectivePoint<C>
+where
+    C: PrimeCurveParams,
+    AffinePoint<C>: FromEncodedPoint<C> + ToEncodedPoint<C>,
+    C::FieldBytesSize: ModulusSize,
+{
+    fn from_xy_u64(x: u64, y: u64) -> Option<Self> {
+        Point::<C>::from_xy_u64(x, y).map(Self::from)
+    }
+
+    fn to_xy_u64(&self) -> Option<(u64, u64)> {
+        Point::<C>::from(*self).to_xy_u64()
+    }
+
+    fn mul_u64(&self, k: u64) -> Self {
+        Point::<C>::from(*self).mul_u64(k).into()
+    }
+}
+
+impl<C> PointU64 for AffinePoint<C>
+where
+    C: PrimeCurveParams,
+    AffinePoint<C>: FromEncodedPoint<C> + ToEncodedPoint<C>,
+    C::FieldBytesSize: ModulusSize,
+{
+    fn from_xy_u64(x: u64, y: u64) -> Option<Self> {
+        ProjectivePoint::<C>::from_xy_u64(x, y).map(|point| point.to_affine())
+    }
+
+    fn to_xy_u64(&self) -> Option<(u64, u64)> {
+        Point::<C>::from(*self).to_xy_u64()
+    }
+
+    fn mul_u64(&self, k: u64) -> Self {
+        ProjectivePoint::<C>::from(*self).mul_u64(k).to_affine()
+    }
+}
+
+impl<C: PrimeCurveParams> AsRef<ProjectivePoint<C>> for Point<C> {
+    fn as_ref(&self) -> &ProjectivePoint<C> {
+        &self.0
+    }
+}
+
+impl<C: PrimeCurveParams> From<Point<C>> for ProjectivePoint<C> {
+    fn from(source: Point<C>) -> Self {
+        source.0
+    }
+}
+
+impl<C> Hash for Point<C>
+where
+    C: PrimeCurveParams,
+    AffinePoint<C>: ToEncodedPoint<C>,
+    C::FieldBytesSize: ModulusSize,
+{
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.0
+            .to_affine()
+            .to_encoded_point(true)
+            .as_bytes()
+            .hash(state)
+    }
+}
+
+impl<C> Display for Point<C>
+where
+    C: PrimeCurveParams,
+    C::FieldElement: Display,
+    AffinePoint<C>: ToEncodedPoint<C>,
+    C::FieldBytesSize: ModulusSize,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.fmt_with(f, Display::fmt)
+    }
+}
+
+impl<C> LowerHex for Point<C>
+where
+    C: PrimeCurveParams,
+    C::FieldElement: LowerHex,
+    AffinePoint<C>: ToEncodedPoint<C>,
+    C::FieldBytesSize: ModulusSize,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.fmt_with(f, LowerHex::fmt)
+    }
+}
+
+impl<C> UpperHex for Point<C>
+where
+    C: PrimeCurveParams,
+    C::FieldElement: UpperHex,
+    AffinePoint<C>: ToEncodedPoint<C>,
+    C::FieldBytesSize: ModulusSize,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.fmt_with(f, UpperHex::fmt)
+    }
+}
+
+impl<C> FromStr for Point<C>
+where
+    C: PrimeCurveParams,
+    C::FieldElement: FromStr<Err = ParseError>,
+    AffinePoint<C>: FromEncodedPoint<C>,
+    C::FieldBytesSize: ModulusSize,
+{
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s == "identity" {
+            return Ok(Self::IDENTITY);
+        }
+
+        let (x, y) = s
+            .strip_prefix('(')
+            .and_then(|s| s.strip_suffix(')'))
+            .and_then(|s| s.split_once(','))
+            .ok_or(ParseError::Invalid)?;
+        let x = x.trim().parse::<C::FieldElement>()?;
+        let y = y.trim().parse::<C::FieldElement>()?;
+
+        Self::from_coordinates(&x, &y).ok_or(ParseError::NotOnCurve)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::{
+        fmt::{Display, LowerHex},
+        str::FromStr,
+    };
+    use std::collections::HashSet;
+
+    use primeorder::{
+        elliptic_curve::{point::Double, CurveArithmetic, Field},
+        AffinePoint, PrimeCurveParams, ProjectivePoint,
+    };
+    use rand_core::OsRng;
+
+    use crate::{
+        curve16, curve64, FieldElement16, FieldElement32, ParseError, Point, Point16, Point64,
+        PointU64, Scalar16, Scalar64, TinyCurve16, TinyCurve32, TinyCurve64,
+    };
+
+    #[test]
+    fn field_element_formatting() {
+        let x = Scalar16::from(0xabcu64);
+        assert_eq!(x.to_string(), "2748");
+        assert_eq!(format!("{x:x}"), "abc");
+        assert_eq!(format!("{x:#06X}"), "0x0ABC");
+
+        assert_eq!("2748".parse::<Scalar16>(), Ok(x));
+        assert_eq!("0xabc".parse::<Scalar16>(), Ok(x));
+        assert_eq!("0xABC".parse::<Scalar16>(), Ok(x));
+
+        assert_eq!("0xfe93".parse::<Scalar16>(), Err(ParseError::OutOfRange));
+        assert_eq!("0xfe92".parse::<Scalar16>(), Ok(-Scalar16::ONE));
+        assert_eq!(
+            "18446744073709551616".parse::<Scalar64>(),
+            Err(ParseError::OutOfRange)
+        );
+        for s in ["", "0x", "abc", "-1", "1.0", " 1", "+5", "0x+1", "+0x1"] {
+            assert_eq!(s.parse::<FieldElement16>(), Err(ParseError::Invalid));
+        }
+
+        let x = FieldElement32::random(&mut OsRng);
+        assert_eq!(x.to_string().parse::<FieldElement32>(), Ok(x));
+        assert_eq!(format!("{x:#x}").parse::<FieldElement32>(), Ok(x));
+    }
+
+    #[test]
+    fn inherent_methods() {
+        let x = Scalar16::random(&mut OsRng);
+        assert_eq!(x.pow([3]), x * x * x);
+        if !bool::from(x.is_zero()) {
+            assert_eq!(x.invert_vartime().unwrap() * x, Scalar16::ONE);
+        }
+        assert!(bool::from(Scalar16::ZERO.invert_vartime().is_none()));
+    }
+
+    #[test]
+    fn integer_interop() {
+        assert_eq!(Scalar16::try_from_u64(0xfe92), Some(-Scalar16::ONE));
+        assert_eq!(Scalar16::try_from_u64(0xfe93), None);
+        assert_eq!(Scalar16::from_u64_reduced(0xfe93), Scalar16::ZERO);
+        assert_eq!(Scalar16::from(0xfe94u64), Scalar16::ONE);
+        assert_eq!(Scalar64::from(u64::MAX).to_u64(), u64::MAX % curve64::ORDER);
+        assert_eq!(u64::from(-FieldElement16::ONE), curve16::FIELD_MODULUS - 1);
+
+        let (x, y) = curve16::GENERATOR;
+        assert_eq!(Point16::from_xy_u64(x, y), Some(Point16::GENERATOR));
+        assert_eq!(Point16::GENERATOR.to_xy_u64(), Some((x, y)));
+        assert_eq!(Point16::from_xy_u64(x, y + 1), None);
+        assert_eq!(Point16::from_xy_u64(x + curve16::FIELD_MODULUS, y), None);
+        assert_eq!(Point16::IDENTITY.to_xy_u64(), None);
+
+        let p = Point64::GENERATOR.mul_u64(u64::MAX);
+        assert_eq!(
+            *p.as_ref(),
+            *Point64::GENERATOR.as_ref() * Scalar64::from_u64_reduced(u64::MAX)
+        );
+        assert_eq!(
+            Point16::GENERATOR.mul_u64(curve16::ORDER),
+            Point16::IDENTITY
+        );
+
+        // The same methods for the `primeorder` point types
+        type Affine16 = AffinePoint<TinyCurve16>;
+        type Projective16 = ProjectivePoint<TinyCurve16>;
+        assert_eq!(Affine16::from_xy_u64(x, y), Some(Affine16::GENERATOR));
+        assert_eq!(
+            Projective16::from_xy_u64(x, y),
+            Some(Projective16::GENERATOR)
+        );
+        assert_eq!(Affine16::from_xy_u64(x, y + 1), None);
+        assert_eq!(Affine16::GENERATOR.to_xy_u64(), Some((x, y)));
+        assert_eq!(Projective16::GENERATOR.to_xy_u64(), Some((x, y)));
+        assert_eq!(Affine16::IDENTITY.to_xy_u64(), None);
+        assert_eq!(
+            Affine16::GENERATOR.mul_u64(5),
+            (Projective16::GENERATOR * Scalar16::from(5u64)).to_affine()
+        );
+        assert_eq!(
+            Projective16::GENERATOR.mul_u64(curve16::ORDER + 5),
+            Projective16::GENERATOR * Scalar16::from(5u64)
+        );
+    }
+
+    #[test]
+    fn hashing() {
+        let x = Scalar16::random(&mut OsRng);
+        let set = HashSet::from([x, x + Scalar16::ZERO, x + Scalar16::ONE]);
+        assert_eq!(set.len(), 2);
+
+        // Different projective representations of the same point.
+        let p = Point16::GENERATOR.as_ref() + Point16::GENERATOR.as_ref();
+        let q = Point16::GENERATOR.as_ref().double();
+        let set = HashSet::from([Point16::from(p), Point16::from(q), Point16::IDENTITY]);
+        assert_eq!(set.len(), 2);
+    }
+
+    fn check_point_formatting<C>()
+    where
+        C: PrimeCurveParams,
+        Point<C>: Display + LowerHex + FromStr<Err = ParseError>,
+    {
+        let p = Point::<C>::from(
+            *Point::<C>::GENERATOR.as_ref() * <C as CurveArithmetic>::Scalar::random(&mut OsRng),
+        );
+        assert_eq!(p.to_string().parse::<Point<C>>(), Ok(p));
+        assert_eq!(format!("{p:#x}").parse::<Point<C>>(), Ok(p));
+        assert_eq!(Point::<C>::IDENTITY.to_string(), "identity");
+        assert_eq!("identity".parse::<Point<C>>(), Ok(Point::<C>::IDENTITY));
+    }
+
+    #[test]
+    fn point_formatting() {
+        let (x, y) = TinyCurve16::GENERATOR;
+        assert_eq!(Point16::GENERATOR.coordinates(), Some((x, y)));
+        assert_eq!(Point16::GENERATOR.to_string(), "(23947, 53757)");
+        assert_eq!(format!("{:#x}", Point16::GENERATOR), "(0x5d8b, 0xd1fd)");
+        assert_eq!(format!("{:X}", Point16::GENERATOR), "(5D8B, D1FD)");
+        assert_eq!(Point16::IDENTITY.coordinates(), None);
+
+        assert_eq!(
+            "(23947, 53758)".parse::<Point16>(),
+            Err(ParseError::NotOnCurve)
+        );
+        assert_eq!("(23947 53757)".parse::<Point16>(), Err(ParseError::Invalid));
+        assert_eq!(
+            "(+23947, 53757)".parse::<Point16>(),
+            Err(ParseError::Invalid)
+        );
+        assert_eq!(
+            "(23947, 0x+d1fd)".parse::<Point16>(),
+            Err(ParseError::Invalid)
+        );
+        assert_eq!(
+            "(0xffa7, 1)".parse::<Point16>(),
+            Err(ParseError::OutOfRange)
+        );
+
+        check_point_formatting::<TinyCurve16>();
+        check_point_formatting::<TinyCurve32>();
+        check_point_formatting::<TinyCurve64>();
+    }
+}
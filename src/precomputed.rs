@@ -0,0 +1,390 @@
+//! Fixed-base multiplication with precomputed tables.
+//!
+//! `primeorder`'s points do not expose their coordinates, so the multiplication
+//! is done in a separate projective representation, and the result is converted back
+//! (with a single inversion) at the end.
+
+use primeorder::{
+    elliptic_curve::{
+        ops::Invert,
+        sec1::{EncodedPoint, FromEncodedPoint, ToEncodedPoint},
+        subtle::{Choice, ConditionallySelectable, ConstantTimeEq, CtOption},
+        CurveArithmetic, Field, FieldBytes, Group, PrimeField,
+    },
+    AffinePoint, PrimeCurveParams, ProjectivePoint,
+};
+
+use crate::{
+    batch::batch_normalize,
+    msm::digit,
+    prime_field::{ReprSizeTypenum, ReprUint},
+    TinyCurve16, TinyCurve32, TinyCurve64,
+};
+
+// The window size in bits.
+const WINDOW: usize = 4;
+
+// The number of non-trivial multiples of the base in each window.
+const ENTRIES: usize = (1 << WINDOW) - 1;
+
+// The maximum number of windows (enough for 64-bit scalars).
+const MAX_WINDOWS: usize = u64::BITS as usize / WINDOW;
+
+type Table<F> = [[(F, F); ENTRIES]; MAX_WINDOWS];
+
+/// A table of multiples of a fixed base point, speeding up the multiplication by a scalar.
+///
+/// The multiplication takes one (mixed) point addition per 4 bits of the scalar
+/// and no doublings, and is constant-time with respect to the scalar.
+#[derive(Debug, Clone, Copy)]
+pub struct PrecomputedBase<C: PrimeCurveParams> {
+    // `table[i][j] = (j + 1) * 2^(4 * i) * base` in affine coordinates.
+    table: Table<C::FieldElement>,
+    base_is_identity: u8,
+}
+
+/// Curves with a generator table built at compile time.
+pub trait PrecomputedGenerator: PrimeCurveParams {
+    /// The table for the generator of the curve.
+    const GENERATOR_TABLE: &'static PrecomputedBase<Self>;
+}
+
+impl<C> PrecomputedBase<C>
+where
+    C: PrimeCurveParams,
+{
+    const fn from_table(table: Table<C::FieldElement>) -> Self {
+        Self {
+            table,
+            base_is_identity: 0,
+        }
+    }
+}
+
+impl<C> PrecomputedBase<C>
+where
+    C: PrimeCurveParams<Uint = ReprUint, FieldBytesSize = ReprSizeTypenum>,
+    C::FieldElement: Invert<Output = CtOption<C::FieldElement>>,
+{
+    /// Builds a table for the given base.
+    ///
+    /// Panics if the curve's scalars are wider than 64 bits.
+    pub fn new(base: &ProjectivePoint<C>) -> Self {
+        assert!(
+            Self::windows() <= MAX_WINDOWS,
+            "Scalars wider than 64 bits are not supported"
+        );
+
+        let mut multiples = [ProjectivePoint::<C>::IDENTITY; ENTRIES * MAX_WINDOWS];
+        let mut window_base = *base;
+        for window in multiples.chunks_mut(ENTRIES) {
+            let mut multiple = window_base;
+            for entry in window.iter_mut() {
+                *entry = multiple;
+                multiple += window_base;
+            }
+            // `multiple` is now `2^WINDOW * window_base`.
+            window_base = multiple;
+        }
+
+        let mut affine = [AffinePoint::<C>::IDENTITY; ENTRIES * MAX_WINDOWS];
+        batch_normalize(&multiples, &mut affine);
+
+        let mut table = [[(C::FieldElement::ZERO, C::FieldElement::ZERO); ENTRIES]; MAX_WINDOWS];
+        for (entry, point) in table.iter_mut().flatten().zip(affine.iter()) {
+            let encoded = point.to_encoded_point(false);
+            // The identity is only encountered if the base is the identity,
+            // in which case the table is not used.
+            if let (Some(x), Some(y)) = (encoded.x(), encoded.y()) {
+                *entry = (
+                    C::FieldElement::from_repr(*FieldBytes::<C>::from_slice(x))
+                        .expect("the coordinate is a valid field element"),
+                    C::FieldElement::from_repr(*FieldBytes::<C>::from_slice(y))
+                        .expect("the coordinate is a valid field element"),
+                );
+            }
+        }
+
+        Self {
+            table,
+            base_is_identity: base.is_identity().unwrap_u8(),
+        }
+    }
+
+    fn windows() -> usize {
+        (<C as CurveArithmetic>::Scalar::NUM_BITS as usize).div_ceil(WINDOW)
+    }
+
+    /// Multiplies the base by `scalar`.
+    pub fn mul(&self, scalar: &C::Scalar) -> ProjectivePoint<C> {
+        let repr = scalar.to_repr();
+        let mut acc = Projective::<C>::IDENTITY;
+        for (i, window) in self.table.iter().take(Self::windows()).enumerate() {
+            let digit = digit(&repr, i * WINDOW, WINDOW) as u8;
+            let mut entry = window[0];
+            for (j, candidate) in window.iter().enumerate().skip(1) {
+                let choice = (j as u8 + 1).ct_eq(&digit);
+                entry.0.conditional_assign(&candidate.0, choice);
+                entry.1.conditional_assign(&candidate.1, choice);
+            }
+            let sum = acc.add_mixed(&entry);
+            acc = Projective::select(&sum, &acc, digit.ct_eq(&0));
+        }
+
+        let is_identity = Choice::from(self.base_is_identity) | acc.z.is_zero();
+        acc.to_projective_point(is_identity)
+    }
+}
+
+impl<C> PrecomputedBase<C>
+where
+    C: PrecomputedGenerator,
+{
+    /// Returns the table for the generator of the curve.
+    pub fn generator() -> &'static Self {
+        C::GENERATOR_TABLE
+    }
+}
+
+/// A point in projective coordinates.
+#[derive(Clone, Copy)]
+struct Projective<C: PrimeCurveParams> {
+    x: C::FieldElement,
+    y: C::FieldElement,
+    z: C::FieldElement,
+}
+
+impl<C> Projective<C>
+where
+    C: PrimeCurveParams<Uint = ReprUint, FieldBytesSize = ReprSizeTypenum>,
+{
+    const IDENTITY: Self = Self {
+        x: C::FieldElement::ZERO,
+        y: C::FieldElement::ONE,
+        z: C::FieldElement::ZERO,
+    };
+
+    fn select(lhs: &Self, rhs: &Self, choice: Choice) -> Self {
+        Self {
+            x: C::FieldElement::conditional_select(&lhs.x, &rhs.x, choice),
+            y: C::FieldElement::conditional_select(&lhs.y, &rhs.y, choice),
+            z: C::FieldElement::conditional_select(&lhs.z, &rhs.z, choice),
+        }
+    }
+
+    /// Adds an affine point that is not the identity.
+    ///
+    /// Implements the complete mixed addition formula from [Renes-Costello-Batina 2015]
+    /// (Algorithm 2).
+    ///
+    /// [Renes-Costello-Batina 2015]: https://eprint.iacr.org/2015/1060
+    fn add_mixed(&self, rhs: &(C::FieldElement, C::FieldElement)) -> Self {
+        let (rhs_x, rhs_y) = rhs;
+        let b3 = C::EQUATION_B + C::EQUATION_B + C::EQUATION_B;
+
+        let t0 = self.x * rhs_x;
+        let t1 = self.y * rhs_y;
+        let t3 = (*rhs_x + rhs_y) * (self.x + self.y) - (t0 + t1);
+        let t4 = *rhs_x * self.z + self.x;
+        let t5 = *rhs_y * self.z + self.y;
+        let z3 = b3 * self.z + C::EQUATION_A * t4;
+        let x3 = t1 - z3;
+        let z3 = t1 + z3;
+        let y3 = x3 * z3;
+        let t2 = C::EQUATION_A * self.z;
+        let t1 = t0 + t0 + t0 + t2;
+        let t4 = b3 * t4 + C::EQUATION_A * (t0 - t2);
+
+        Self {
+            x: t3 * x3 - t5 * t4,
+            y: y3 + t1 * t4,
+            z: t5 * z3 + t3 * t1,
+        }
+    }
+
+    fn to_projective_point(self, is_identity: Choice) -> ProjectivePoint<C> {
+        let z_inv = Field::invert(&self.z).unwrap_or(C::FieldElement::ONE);
+        // Use a valid point in place of the identity so that the decoding does not fail.
+        let x =
+            C::FieldElement::conditional_select(&(self.x * z_inv), &C::GENERATOR.0, is_identity);
+        let y =
+            C::FieldElement::conditional_select(&(self.y * z_inv), &C::GENERATOR.1, is_identity);
+
+        let encoded = EncodedPoint::<C>::from_affine_coordinates(&x.to_repr(), &y.to_repr(), false);
+        let affine = Option::<AffinePoint<C>>::from(AffinePoint::<C>::from_encoded_point(&encoded))
+            .expect("the point is on the curve");
+
+        ProjectivePoint::<C>::conditional_select(
+            &affine.into(),
+            &ProjectivePoint::<C>::IDENTITY,
+            is_identity,
+        )
+    }
+}
+
+const fn add_mod(a: u64, b: u64, m: u64) -> u64 {
+    ((a as u128 + b as u128) % m as u128) as u64
+}
+
+const fn sub_mod(a: u64, b: u64, m: u64) -> u64 {
+    add_mod(a, m - b, m)
+}
+
+const fn mul_mod(a: u64, b: u64, m: u64) -> u64 {
+    ((a as u128 * b as u128) % m as u128) as u64
+}
+
+const fn invert_mod(a: u64, m: u64) -> u64 {
+    let mut result = 1;
+    let mut base = a;
+    let mut exponent = m - 2;
+    while exponent > 0 {
+        if exponent & 1 == 1 {
+            result = mul_mod(result, base, m);
+        }
+        base = mul_mod(base, base, m);
+        exponent >>= 1;
+    }
+    result
+}
+
+/// Adds two affine points, neither of which is the identity, and which are not inverses
+/// of each other, on the curve `y^2 = x^3 + a * x + b` over the field with the modulus `m`.
+const fn add_affine(p: (u64, u64), q: (u64, u64), a: u64, m: u64) -> (u64, u64) {
+    let lambda = if p.0 == q.0 {
+        // Since `p != -q`, `p == q`.
+        let numerator = add_mod(mul_mod(3, mul_mod(p.0, p.0, m), m), a, m);
+        mul_mod(numerator, invert_mod(add_mod(p.1, p.1, m), m), m)
+    } else {
+        mul_mod(sub_mod(q.1, p.1, m), invert_mod(sub_mod(q.0, p.0, m), m), m)
+    };
+    let x = sub_mod(sub_mod(mul_mod(lambda, lambda, m), p.0, m), q.0, m);
+    let y = sub_mod(mul_mod(lambda, sub_mod(p.0, x, m), m), p.1, m);
+    (x, y)
+}
+
+/// Builds the table for the given base point in constant context,
+/// with the coordinates as integers in `[0, m)`.
+const fn raw_table(base: (u64, u64), a: u64, m: u64) -> Table<u64> {
+    let mut table = [[(0, 0); ENTRIES]; MAX_WINDOWS];
+    let mut window_base = base;
+    let mut i = 0;
+    while i < MAX_WINDOWS {
+        // Since the curve order is a prime greater than `2^WINDOW`,
+        // none of the multiples is the identity or an inverse of the base.
+        let mut multiple = window_base;
+        let mut j = 0;
+        while j < ENTRIES {
+            table[i][j] = multiple;
+            multiple = add_affine(multiple, window_base, a, m);
+            j += 1;
+        }
+        window_base = multiple;
+        i += 1;
+    }
+    table
+}
+
+macro_rules! impl_precomputed_generator {
+    ($curve:ty, $uint:ty, $modulus:expr, $generator:expr) => {
+        impl PrecomputedGenerator for $curve {
+            // The casts are trivial for `u64`.
+            #[allow(trivial_numeric_casts)]
+            const GENERATOR_TABLE: &'static PrecomputedBase<Self> = &{
+                type F = <$curve as PrimeCurveParams>::FieldElement;
+                // All the tiny curves have `a = -3`.
+                let raw = raw_table($generator, $modulus - 3, $modulus);
+                let mut table = [[(F::ZERO, F::ZERO); ENTRIES]; MAX_WINDOWS];
+                let mut i = 0;
+                while i < MAX_WINDOWS {
+                    let mut j = 0;
+                    while j < ENTRIES {
+                        table[i][j] = (
+                            F::new_unchecked(raw[i][j].0 as $uint),
+                            F::new_unchecked(raw[i][j].1 as $uint),
+                        );
+                        j += 1;
+                    }
+                    i += 1;
+                }
+                PrecomputedBase::from_table(table)
+            };
+        }
+    };
+}
+
+impl_precomputed_generator!(
+    TinyCurve16,
+    u16,
+    crate::curve16::FIELD_MODULUS,
+    crate::curve16::GENERATOR
+);
+impl_precomputed_generator!(
+    TinyCurve32,
+    u32,
+    crate::curve32::FIELD_MODULUS,
+    crate::curve32::GENERATOR
+);
+impl_precomputed_generator!(
+    TinyCurve64,
+    u64,
+    crate::curve64::FIELD_MODULUS,
+    crate::curve64::GENERATOR
+);
+
+#[cfg(test)]
+mod tests {
+    use primeorder::{
+        elliptic_curve::{ops::Invert, subtle::CtOption, CurveArithmetic, Field},
+        PrimeCurveParams, ProjectivePoint,
+    };
+    use rand_core::OsRng;
+
+    use super::{PrecomputedBase, PrecomputedGenerator};
+    use crate::{
+        prime_field::{ReprSizeTypenum, ReprUint},
+        TinyCurve16, TinyCurve32, TinyCurve64,
+    };
+
+    fn check_precomputed<C>()
+    where
+        C: PrecomputedGenerator
+            + PrimeCurveParams<Uint = ReprUint, FieldBytesSize = ReprSizeTypenum>,
+        C::FieldElement: Invert<Output = CtOption<C::FieldElement>>,
+    {
+        type Scalar<C> = <C as CurveArithmetic>::Scalar;
+
+        let generator = PrecomputedBase::<C>::generator();
+        assert_eq!(
+            generator.table,
+            PrecomputedBase::new(&ProjectivePoint::<C>::GENERATOR).table
+        );
+
+        let base = ProjectivePoint::<C>::GENERATOR * Scalar::<C>::random(&mut OsRng);
+        let precomputed = PrecomputedBase::new(&base);
+        let identity = PrecomputedBase::new(&ProjectivePoint::<C>::IDENTITY);
+
+        let scalars = [
+            Scalar::<C>::ZERO,
+            Scalar::<C>::ONE,
+            -Scalar::<C>::ONE,
+            Scalar::<C>::random(&mut OsRng),
+            Scalar::<C>::random(&mut OsRng),
+        ];
+        for scalar in scalars {
+            assert_eq!(
+                generator.mul(&scalar),
+                ProjectivePoint::<C>::GENERATOR * scalar
+            );
+            assert_eq!(precomputed.mul(&scalar), base * scalar);
+            assert_eq!(identity.mul(&scalar), ProjectivePoint::<C>::IDENTITY);
+        }
+    }
+
+    #[test]
+    fn precomputed() {
+        check_precomputed::<TinyCurve16>();
+        check_precomputed::<TinyCurve32>();
+        check_precomputed::<TinyCurve64>();
+    }
+}
@@ -1,13 +1,17 @@
 use core::{
-    fmt::Debug,
+    cmp::Ordering,
+    fmt::{self, Debug, Display, LowerHex, UpperHex},
+    hash::{Hash, Hasher},
     iter::{Product, Sum},
+    num::IntErrorKind,
     ops::{Add, AddAssign, Mul, MulAssign, Neg, ShrAssign, Sub, SubAssign},
+    str::FromStr,
 };
 
 use num_traits::{ConstZero, FromBytes, ToBytes};
 use primeorder::elliptic_curve::{
-    bigint::{Encoding, NonZero, U192},
-    ff::helpers::sqrt_ratio_generic,
+    bigint::{ArrayEncoding, ByteArray, Encoding, NonZero, Uint, U192, U512},
+    ff::{helpers::sqrt_ratio_generic, FromUniformBytes},
     generic_array::{typenum, GenericArray},
     ops::{Invert, Reduce, ReduceNonZero},
     rand_core::RngCore,
@@ -26,9 +30,15 @@ use ::{
     },
 };
 
+#[cfg(feature = "bits")]
+use primeorder::elliptic_curve::ff::{FieldBits, PrimeFieldBits};
+
+#[cfg(feature = "instrument")]
+use crate::instrumented::{self, Op};
 use crate::{
     primitives::{add, modular_inverse, mul, neg, sub},
-    traits::{Modulus, PrimeFieldConstants, PrimitiveUint},
+    secret::{self, VartimeInfo, VartimeOps},
+    traits::{to_internal_const, InternalConstants, Modulus, PrimeFieldConstants, PrimitiveUint},
 };
 
 // The external representation of a field element.
@@ -43,31 +53,199 @@ pub(crate) type ReprUint = U192;
 // a dependency of `primeorder=0.14`.
 pub(crate) type ReprSizeTypenum = typenum::U24;
 
-#[derive(Default, Debug, Clone, Copy, PartialOrd, Ord, PartialEq, Eq)]
+/// An element of the prime field modulo `M`, stored in an integer type `T` (`u16`, `u32` or `u64`).
+///
+/// The scalars and the field elements of the curves in this crate
+/// ([`Scalar16`](crate::Scalar16), [`FieldElement16`](crate::FieldElement16) and so on)
+/// are aliases of this type, so code generic over them can be written in terms of it.
+///
+/// Implements [`PrimeField`], and additionally provides the integer conversions
+/// ([`try_from_u64`](Self::try_from_u64), [`from_u64_reduced`](Self::from_u64_reduced),
+/// [`to_u64`](Self::to_u64)), [`pow`](Self::pow) and [`invert_vartime`](Self::invert_vartime)
+/// available without importing the traits, and [`batch_invert`](Self::batch_invert).
+// The value is stored in the internal representation (see `PrimitiveUint::to_internal()`).
+#[derive(Default, Clone, Copy, PartialEq, Eq)]
 pub struct FieldElement<T: PrimitiveUint, const M: u64>(T);
 
 impl<T, const M: u64> FieldElement<T, M>
 where
     T: PrimitiveUint,
 {
-    pub(crate) const fn new_unchecked(value: T) -> Self {
+    /// Wraps a value in the internal representation.
+    #[cfg(feature = "alloc")]
+    pub(crate) const fn from_internal(value: T) -> Self {
         Self(value)
     }
 
+    /// Returns the value in the internal representation.
+    #[cfg(feature = "alloc")]
+    pub(crate) fn into_internal(self) -> T {
+        self.0
+    }
+
     fn new_unchecked_u64(value: u64) -> Self {
         debug_assert!(value < M);
-        Self(T::from_u64(value).expect("the value is less than the modulus and therefore fits `T`"))
+        Self(
+            T::from_u64(value)
+                .expect("the value is less than the modulus and therefore fits `T`")
+                .to_internal::<M>(),
+        )
+    }
+
+    /// Creates an element from its canonical value, returning `None` if it is not less than the modulus.
+    ///
+    /// (`TryFrom<u64>` cannot be implemented since `PrimeField` requires `From<u64>`.)
+    pub fn try_from_u64(value: u64) -> Option<Self> {
+        (value < M).then(|| Self::new_unchecked_u64(value))
+    }
+
+    /// Creates an element from `value` modulo the modulus.
+    pub fn from_u64_reduced(value: u64) -> Self {
+        Self::new_unchecked_u64(value % M)
+    }
+
+    /// Returns the canonical value of the element.
+    pub fn to_u64(self) -> u64 {
+        self.0.to_canonical::<M>().into()
+    }
+}
+
+macro_rules! impl_new_unchecked {
+    ($t:ty) => {
+        // The casts are trivial for `u64`.
+        #[allow(trivial_numeric_casts)]
+        impl<const M: u64> FieldElement<$t, M> {
+            pub(crate) const fn new_unchecked(value: $t) -> Self {
+                debug_assert!((value as u64) < M);
+                Self(to_internal_const(value as u64, M, <$t>::BITS) as $t)
+            }
+        }
+    };
+}
+
+impl_new_unchecked!(u16);
+impl_new_unchecked!(u32);
+impl_new_unchecked!(u64);
+
+impl<T, const M: u64> Debug for FieldElement<T, M>
+where
+    T: PrimitiveUint,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("FieldElement")
+            .field(&self.0.to_canonical::<M>())
+            .finish()
+    }
+}
+
+/// An error returned when parsing a field element or a point from a string.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ParseError {
+    /// The string is not a decimal or a `0x`-prefixed hexadecimal number (or a pair of them).
+    Invalid,
+    /// The value is not less than the modulus.
+    OutOfRange,
+    /// The coordinates do not belong to a point on the curve.
+    NotOnCurve,
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let message = match self {
+            Self::Invalid => "invalid number",
+            Self::OutOfRange => "the value is not less than the modulus",
+            Self::NotOnCurve => "the point is not on the curve",
+        };
+        f.write_str(message)
+    }
+}
+
+// Formatting uses the canonical value, in the same way as `u64` formats it.
+impl<T, const M: u64> Display for FieldElement<T, M>
+where
+    T: PrimitiveUint,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        Display::fmt(&self.to_u64(), f)
+    }
+}
+
+impl<T, const M: u64> LowerHex for FieldElement<T, M>
+where
+    T: PrimitiveUint,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        LowerHex::fmt(&self.to_u64(), f)
     }
+}
+
+impl<T, const M: u64> UpperHex for FieldElement<T, M>
+where
+    T: PrimitiveUint,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        UpperHex::fmt(&self.to_u64(), f)
+    }
+}
 
-    fn to_u64(self) -> u64 {
-        self.0.into()
+/// Parses a decimal or a `0x`-prefixed hexadecimal number (without a sign).
+impl<T, const M: u64> FromStr for FieldElement<T, M>
+where
+    T: PrimitiveUint,
+{
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (digits, radix) = match s.strip_prefix("0x") {
+            Some(hex) => (hex, 16),
+            None => (s, 10),
+        };
+        // `from_str_radix()` accepts a leading `+`
+        if digits.starts_with('+') {
+            return Err(ParseError::Invalid);
+        }
+        let value = u64::from_str_radix(digits, radix).map_err(|err| match err.kind() {
+            IntErrorKind::PosOverflow => ParseError::OutOfRange,
+            _ => ParseError::Invalid,
+        })?;
+
+        Self::try_from_u64(value).ok_or(ParseError::OutOfRange)
+    }
+}
+
+// The internal representation is a bijection, so hashing the canonical value agrees with `Eq`.
+impl<T, const M: u64> Hash for FieldElement<T, M>
+where
+    T: PrimitiveUint,
+{
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.to_u64().hash(state)
+    }
+}
+
+// Compare canonical values so that the order does not depend on the internal representation.
+impl<T, const M: u64> PartialOrd for FieldElement<T, M>
+where
+    T: PrimitiveUint,
+{
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<T, const M: u64> Ord for FieldElement<T, M>
+where
+    T: PrimitiveUint,
+{
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0.to_canonical::<M>().cmp(&other.0.to_canonical::<M>())
     }
 }
 
 impl<T, const M: u64> FieldElement<T, M>
 where
     T: PrimitiveUint,
-    Modulus<T, M>: PrimeFieldConstants<T>,
+    Modulus<T, M>: PrimeFieldConstants<T> + InternalConstants<T>,
 {
     #[cfg(test)]
     pub(crate) const MODULUS: T = Modulus::<T, M>::MODULUS;
@@ -84,11 +262,85 @@ where
         Self(mul::<T, M>(&self.0, &rhs.0))
     }
 
+    /// Raises the element to the power `exp` (given as little-endian 64-bit limbs).
+    ///
+    /// Same as [`Field::pow`], available without importing the trait.
+    pub fn pow<S: AsRef<[u64]>>(&self, exp: S) -> Self {
+        Field::pow(self, exp)
+    }
+
+    /// Inverts the element in variable time (fails for zero).
+    ///
+    /// Same as [`Invert::invert_vartime`], available without importing the trait.
+    pub fn invert_vartime(&self) -> CtOption<Self> {
+        Invert::invert_vartime(self)
+    }
+
+    /// Inverts the element as `x^(M-2)` (the exponent is public, so the time does not depend on `x`).
+    fn invert_fermat(&self) -> CtOption<Self> {
+        let inverse = self.pow_public(&[M - 2]);
+        CtOption::new(inverse, !self.is_zero())
+    }
+
+    /// Inverts all of `elements` with a single inversion (Montgomery's trick),
+    /// writing the results to `inverses`.
+    ///
+    /// Zero elements are mapped to zero and do not affect the other results.
+    ///
+    /// Panics if the slices have different lengths.
+    pub fn batch_invert(elements: &[Self], inverses: &mut [Self]) {
+        assert_eq!(
+            elements.len(),
+            inverses.len(),
+            "The output must have the same length as the input"
+        );
+
+        // `inverses[i]` is set to the product of the non-zero elements before `i`.
+        let mut acc = Self::ONE;
+        for (element, inverse) in elements.iter().zip(inverses.iter_mut()) {
+            *inverse = acc;
+            acc = Self::conditional_select(&(acc * element), &acc, element.is_zero());
+        }
+
+        // The accumulator is a product of non-zero elements, so the inversion succeeds.
+        let mut acc_inv = Field::invert(&acc).unwrap_or(Self::ZERO);
+        for (element, inverse) in elements.iter().zip(inverses.iter_mut()).rev() {
+            let is_zero = element.is_zero();
+            let result = *inverse * acc_inv;
+            acc_inv = Self::conditional_select(&(acc_inv * element), &acc_inv, is_zero);
+            *inverse = Self::conditional_select(&result, &Self::ZERO, is_zero);
+        }
+    }
+
+    /// Raises the element to the power `exp` (given as little-endian 64-bit limbs)
+    /// in time depending only on `exp`.
+    ///
+    /// Not reported as a variable-time primitive, so `exp` must be public.
+    fn pow_public(&self, exp: &[u64]) -> Self {
+        let mut res = Self::ONE;
+        for limb in exp.iter().rev() {
+            for i in (0..u64::BITS).rev() {
+                res = res.square();
+                if (limb >> i) & 1 == 1 {
+                    res *= self;
+                }
+            }
+        }
+        res
+    }
+
     pub(crate) fn sqrt(&self) -> CtOption<Self> {
+        #[cfg(feature = "instrument")]
+        return instrumented::record_composite(Op::FieldSqrt, || self.sqrt_uninstrumented());
+        #[cfg(not(feature = "instrument"))]
+        self.sqrt_uninstrumented()
+    }
+
+    fn sqrt_uninstrumented(&self) -> CtOption<Self> {
         // All our moduli are chosen so that they are 3 mod 4.
         debug_assert!(M & 3 == 3);
         // This means calculating the square root can be done via exponentiation.
-        let res = self.pow_vartime([(M >> 2) + 1]);
+        let res = self.pow_public(&[(M >> 2) + 1]);
         let is_square = res.square().ct_eq(self);
         CtOption::new(res, is_square)
     }
@@ -123,13 +375,22 @@ where
     }
 }
 
+/// Reduces the value modulo the modulus (see [`FieldElement::try_from_u64`] for a strict version).
 impl<T, const M: u64> From<u64> for FieldElement<T, M>
 where
     T: PrimitiveUint,
 {
     fn from(source: u64) -> Self {
-        debug_assert!(source < M);
-        Self::new_unchecked_u64(source)
+        Self::from_u64_reduced(source)
+    }
+}
+
+impl<T, const M: u64> From<FieldElement<T, M>> for u64
+where
+    T: PrimitiveUint,
+{
+    fn from(source: FieldElement<T, M>) -> Self {
+        source.to_u64()
     }
 }
 
@@ -181,13 +442,23 @@ where
 impl<T, const M: u64> Invert for FieldElement<T, M>
 where
     T: PrimitiveUint,
+    Modulus<T, M>: PrimeFieldConstants<T> + InternalConstants<T>,
 {
     type Output = CtOption<Self>;
 
     fn invert(&self) -> Self::Output {
-        let inverse = modular_inverse::<T, M>(&self.0);
+        #[cfg(feature = "instrument")]
+        return instrumented::record_composite(Op::FieldInvert, || self.invert_fermat());
+        #[cfg(not(feature = "instrument"))]
+        self.invert_fermat()
+    }
+
+    fn invert_vartime(&self) -> Self::Output {
+        #[cfg(feature = "instrument")]
+        instrumented::record(Op::FieldInvert);
+        let inverse = modular_inverse::<T, M>(&self.0.to_canonical::<M>());
         match inverse {
-            Some(inv) => CtOption::new(Self(inv), Choice::from(1)),
+            Some(inv) => CtOption::new(Self(inv.to_internal::<M>()), Choice::from(1)),
             None => CtOption::new(Self(T::ZERO), Choice::from(0)),
         }
     }
@@ -202,63 +473,78 @@ where
     }
 }
 
-impl<T, const M: u64> Reduce<ReprUint> for FieldElement<T, M>
+// Reduction of wider integers (e.g. hash outputs).
+// Since the moduli are at most 64 bits, the bias of reducing an `n`-bit uniformly distributed
+// integer is at most `2^(64-n)`, which is negligible for `U256` and `U512`.
+impl<T, const M: u64, const LIMBS: usize> Reduce<Uint<LIMBS>> for FieldElement<T, M>
 where
     T: PrimitiveUint,
+    Uint<LIMBS>: ArrayEncoding,
 {
-    type Bytes = GenericArray<u8, ReprSizeTypenum>;
-
-    fn reduce(n: ReprUint) -> Self {
-        const DATA_SIZE: usize = u64::BITS as usize / 8;
+    type Bytes = ByteArray<Uint<LIMBS>>;
 
+    fn reduce(n: Uint<LIMBS>) -> Self {
         // TODO: use `rem_vartime()` when the crypto stack switches to crypto-bigint 0.6
-        let reduced = n.rem(&NonZero::new(ReprUint::from(M)).expect("the modulus is non-zero"));
-
-        let bytes = reduced.to_be_bytes();
-        let value_bytes: [u8; DATA_SIZE] = bytes[bytes.len() - DATA_SIZE..]
-            .try_into()
-            .expect("slice has the correct length");
-        Self::new_unchecked_u64(u64::from_be_bytes(value_bytes))
+        let reduced = n.rem(&NonZero::new(Uint::from_u64(M)).expect("the modulus is non-zero"));
+        Self::new_unchecked_u64(uint_to_u64(&reduced))
     }
 
     fn reduce_bytes(bytes: &Self::Bytes) -> Self {
-        let uint = ReprUint::from_be_slice(bytes);
-        Self::reduce(uint)
+        Self::reduce(Uint::from_be_byte_array(bytes.clone()))
     }
 }
 
-impl<T, const M: u64> ReduceNonZero<ReprUint> for FieldElement<T, M>
+impl<T, const M: u64, const LIMBS: usize> ReduceNonZero<Uint<LIMBS>> for FieldElement<T, M>
 where
     T: PrimitiveUint,
+    Uint<LIMBS>: ArrayEncoding,
 {
-    fn reduce_nonzero(n: ReprUint) -> Self {
-        const DATA_SIZE: usize = u64::BITS as usize / 8;
-
+    fn reduce_nonzero(n: Uint<LIMBS>) -> Self {
         // TODO: use `rem_vartime()` when the crypto stack switches to crypto-bigint 0.6
         let reduced = n.rem(
-            &NonZero::new(ReprUint::from(M - 1))
+            &NonZero::new(Uint::from_u64(M - 1))
                 .expect("the modulus is non-zero and greater than 1"),
         );
-
-        let bytes = reduced.to_be_bytes();
-        let value_bytes: [u8; DATA_SIZE] = bytes[bytes.len() - DATA_SIZE..]
-            .try_into()
-            .expect("slice has the correct length");
-        Self::new_unchecked_u64(u64::from_be_bytes(value_bytes) + 1)
+        Self::new_unchecked_u64(uint_to_u64(&reduced) + 1)
     }
 
     fn reduce_nonzero_bytes(bytes: &Self::Bytes) -> Self {
-        let uint = ReprUint::from_be_slice(bytes);
-        Self::reduce_nonzero(uint)
+        Self::reduce_nonzero(Uint::from_be_byte_array(bytes.clone()))
     }
 }
 
+/// Interprets 64 big-endian bytes as an integer and reduces it modulo the modulus.
+impl<T, const M: u64> FromUniformBytes<64> for FieldElement<T, M>
+where
+    T: PrimitiveUint,
+    Modulus<T, M>: PrimeFieldConstants<T> + InternalConstants<T>,
+{
+    fn from_uniform_bytes(bytes: &[u8; 64]) -> Self {
+        Self::reduce(U512::from_be_slice(bytes))
+    }
+}
+
+/// Returns the lowest 64 bits of `uint`.
+fn uint_to_u64<const LIMBS: usize>(uint: &Uint<LIMBS>) -> u64
+where
+    Uint<LIMBS>: ArrayEncoding,
+{
+    const DATA_SIZE: usize = u64::BITS as usize / 8;
+    let bytes = uint.to_be_byte_array();
+    let value_bytes: [u8; DATA_SIZE] = bytes[bytes.len() - DATA_SIZE..]
+        .try_into()
+        .expect("slice has the correct length");
+    u64::from_be_bytes(value_bytes)
+}
+
 impl<T, const M: u64> ShrAssign<usize> for FieldElement<T, M>
 where
     T: PrimitiveUint,
 {
     fn shr_assign(&mut self, shift: usize) {
-        self.0.shr_assign(shift)
+        let mut value = self.0.to_canonical::<M>();
+        value.shr_assign(shift);
+        self.0 = value.to_internal::<M>();
     }
 }
 
@@ -269,6 +555,8 @@ where
     T: PrimitiveUint,
 {
     fn add_assign(&mut self, rhs: &'a FieldElement<T, M>) {
+        #[cfg(feature = "instrument")]
+        instrumented::record(Op::FieldAdd);
         *self = Self(add::<T, M>(&self.0, &rhs.0))
     }
 }
@@ -311,6 +599,8 @@ where
     T: PrimitiveUint,
 {
     fn sub_assign(&mut self, rhs: &'a FieldElement<T, M>) {
+        #[cfg(feature = "instrument")]
+        instrumented::record(Op::FieldAdd);
         *self = Self(sub::<T, M>(&self.0, &rhs.0))
     }
 }
@@ -353,6 +643,8 @@ where
     T: PrimitiveUint,
 {
     fn mul_assign(&mut self, rhs: &'a FieldElement<T, M>) {
+        #[cfg(feature = "instrument")]
+        instrumented::record(Op::FieldMul);
         *self = Self(mul::<T, M>(&self.0, &rhs.0))
     }
 }
@@ -455,10 +747,10 @@ where
 impl<T, const M: u64> Field for FieldElement<T, M>
 where
     T: PrimitiveUint,
-    Modulus<T, M>: PrimeFieldConstants<T>,
+    Modulus<T, M>: PrimeFieldConstants<T> + InternalConstants<T>,
 {
     const ZERO: Self = Self(T::ZERO);
-    const ONE: Self = Self(T::ONE);
+    const ONE: Self = Self(Modulus::<T, M>::ONE);
 
     fn random(mut rng: impl RngCore) -> Self {
         let mut buffer = T::Wide::ZERO.to_be_bytes();
@@ -467,9 +759,9 @@ where
     }
 
     fn square(&self) -> Self {
-        Self(T::reduce_from_wide::<M>(
-            self.0.to_wide() * self.0.to_wide(),
-        ))
+        #[cfg(feature = "instrument")]
+        instrumented::record(Op::FieldSquare);
+        Self(T::mul_internal::<M>(&self.0, &self.0))
     }
 
     fn double(&self) -> Self {
@@ -484,6 +776,11 @@ where
         self.sqrt()
     }
 
+    fn pow_vartime<S: AsRef<[u64]>>(&self, exp: S) -> Self {
+        secret::reached(VartimeOps::EXPONENTIATION);
+        self.pow_public(exp.as_ref())
+    }
+
     fn sqrt_ratio(num: &Self, div: &Self) -> (Choice, Self) {
         // Note that this relies on `Self::sqrt()`,
         // so in order to avoid infinite recurrence, that has to be overridden as well.
@@ -494,20 +791,21 @@ where
 impl<T, const M: u64> PrimeField for FieldElement<T, M>
 where
     T: PrimitiveUint,
-    Modulus<T, M>: PrimeFieldConstants<T>,
+    Modulus<T, M>: PrimeFieldConstants<T> + InternalConstants<T>,
 {
     type Repr = <Modulus<T, M> as PrimeFieldConstants<T>>::Repr;
 
     const MODULUS: &'static str = Modulus::<T, M>::MODULUS_STR;
     const NUM_BITS: u32 = Modulus::<T, M>::NUM_BITS;
     const CAPACITY: u32 = Modulus::<T, M>::CAPACITY;
-    const TWO_INV: Self = FieldElement::new_unchecked(Modulus::<T, M>::TWO_INV);
+    const TWO_INV: Self = Self(<Modulus<T, M> as InternalConstants<T>>::TWO_INV);
     const MULTIPLICATIVE_GENERATOR: Self =
-        FieldElement::new_unchecked(Modulus::<T, M>::MULTIPLICATIVE_GENERATOR);
+        Self(<Modulus<T, M> as InternalConstants<T>>::MULTIPLICATIVE_GENERATOR);
     const S: u32 = Modulus::<T, M>::S;
-    const ROOT_OF_UNITY: Self = FieldElement::new_unchecked(Modulus::<T, M>::ROOT_OF_UNITY);
-    const ROOT_OF_UNITY_INV: Self = FieldElement::new_unchecked(Modulus::<T, M>::ROOT_OF_UNITY_INV);
-    const DELTA: Self = FieldElement::new_unchecked(Modulus::<T, M>::DELTA);
+    const ROOT_OF_UNITY: Self = Self(<Modulus<T, M> as InternalConstants<T>>::ROOT_OF_UNITY);
+    const ROOT_OF_UNITY_INV: Self =
+        Self(<Modulus<T, M> as InternalConstants<T>>::ROOT_OF_UNITY_INV);
+    const DELTA: Self = Self(<Modulus<T, M> as InternalConstants<T>>::DELTA);
 
     fn from_repr(repr: Self::Repr) -> CtOption<Self> {
         const DATA_SIZE: usize = u64::BITS as usize / 8;
@@ -545,6 +843,37 @@ where
     }
 }
 
+// The bit representation has the size of the underlying integer type,
+// so that it does not contain the padding of `Repr`.
+#[cfg(feature = "bits")]
+macro_rules! impl_prime_field_bits {
+    ($t:ty) => {
+        // The casts are trivial for `u64`.
+        #[allow(trivial_numeric_casts)]
+        impl<const M: u64> PrimeFieldBits for FieldElement<$t, M>
+        where
+            Modulus<$t, M>: PrimeFieldConstants<$t> + InternalConstants<$t>,
+        {
+            type ReprBits = $t;
+
+            fn to_le_bits(&self) -> FieldBits<Self::ReprBits> {
+                FieldBits::new(self.to_u64() as $t)
+            }
+
+            fn char_le_bits() -> FieldBits<Self::ReprBits> {
+                FieldBits::new(M as $t)
+            }
+        }
+    };
+}
+
+#[cfg(feature = "bits")]
+impl_prime_field_bits!(u16);
+#[cfg(feature = "bits")]
+impl_prime_field_bits!(u32);
+#[cfg(feature = "bits")]
+impl_prime_field_bits!(u64);
+
 impl<T, const M: u64> AsRef<FieldElement<T, M>> for FieldElement<T, M>
 where
     T: PrimitiveUint,
@@ -554,11 +883,22 @@ where
     }
 }
 
+impl<T, const M: u64> VartimeInfo for FieldElement<T, M>
+where
+    T: PrimitiveUint,
+{
+    const MUL: VartimeOps = VartimeOps::NONE;
+    const INVERT: VartimeOps = VartimeOps::NONE;
+    const INVERT_VARTIME: VartimeOps = VartimeOps::INVERSION;
+    // The exponentiation is only variable-time in the exponent, which is public.
+    const SQRT: VartimeOps = VartimeOps::NONE;
+}
+
 #[cfg(feature = "ecdsa")]
 impl<C, T, const M: u64> SignPrimitive<C> for FieldElement<T, M>
 where
     T: PrimitiveUint,
-    Modulus<T, M>: PrimeFieldConstants<T>,
+    Modulus<T, M>: PrimeFieldConstants<T> + InternalConstants<T>,
     C: PrimeCurve + CurveArithmetic<Scalar = Self>,
     SignatureSize<C>: ArrayLength<u8>,
     Self: Reduce<C::Uint, Bytes = FieldBytes<C>>
@@ -566,3 +906,44 @@ where
         + Into<FieldBytes<C>>,
 {
 }
+
+#[cfg(all(test, feature = "bits"))]
+mod tests_bits {
+    use primeorder::elliptic_curve::{ff::PrimeFieldBits, CurveArithmetic};
+    use primeorder::PrimeCurveParams;
+    use rand_core::OsRng;
+
+    use crate::{TinyCurve16, TinyCurve32, TinyCurve64};
+
+    fn check_bits<F: PrimeFieldBits>(bits: usize) {
+        let x = F::random(&mut OsRng);
+        let repr = x.to_repr();
+        let repr = repr.as_ref();
+        let le_bits = x.to_le_bits();
+        assert_eq!(le_bits.len(), bits);
+        for (i, bit) in le_bits.iter().enumerate() {
+            let byte = repr[repr.len() - 1 - i / 8];
+            assert_eq!(*bit, (byte >> (i % 8)) & 1 == 1);
+        }
+        // The representation has no bits set beyond the integer size.
+        assert!(repr[..repr.len() - bits / 8].iter().all(|byte| byte == &0));
+
+        let modulus_bits = F::char_le_bits();
+        assert_eq!(modulus_bits.len(), bits);
+        let minus_one = (-F::ONE).to_le_bits();
+        // `M - 1` only differs from `M` in the lowest bit, since `M` is odd.
+        assert!(modulus_bits[0]);
+        assert!(!minus_one[0]);
+        assert_eq!(modulus_bits[1..], minus_one[1..]);
+    }
+
+    #[test]
+    fn bits() {
+        check_bits::<<TinyCurve16 as CurveArithmetic>::Scalar>(16);
+        check_bits::<<TinyCurve16 as PrimeCurveParams>::FieldElement>(16);
+        check_bits::<<TinyCurve32 as CurveArithmetic>::Scalar>(32);
+        check_bits::<<TinyCurve32 as PrimeCurveParams>::FieldElement>(32);
+        check_bits::<<TinyCurve64 as CurveArithmetic>::Scalar>(64);
+        check_bits::<<TinyCurve64 as PrimeCurveParams>::FieldElement>(64);
+    }
+}
@@ -1,4 +1,7 @@
-use crate::traits::PrimitiveUint;
+use crate::{
+    secret::{self, VartimeOps},
+    traits::PrimitiveUint,
+};
 
 pub(crate) fn add<T, const M: u64>(lhs: &T, rhs: &T) -> T
 where
@@ -30,7 +33,7 @@ pub(crate) fn mul<T, const M: u64>(lhs: &T, rhs: &T) -> T
 where
     T: PrimitiveUint,
 {
-    T::reduce_from_wide::<M>(lhs.to_wide() * rhs.to_wide())
+    T::mul_internal::<M>(lhs, rhs)
 }
 
 pub(crate) fn neg<T, const M: u64>(arg: &T) -> T
@@ -49,6 +52,7 @@ pub(crate) fn modular_inverse<T, const M: u64>(arg: &T) -> Option<T>
 where
     T: PrimitiveUint,
 {
+    secret::reached(VartimeOps::INVERSION);
     let modulus = T::from_u64(M).expect("the modulus fits into `T`");
 
     // Using Extended Euclidean algorithm.
@@ -0,0 +1,173 @@
+//! Public key recovery from ECDSA signatures.
+//!
+//! For the curves in this crate the field modulus exceeds the order by a noticeable margin
+//! (e.g. `0xffa7` vs `0xfe93` for [`TinyCurve16`](crate::TinyCurve16)), so the x-coordinate
+//! of `R` is reduced (and the recovery id is 2 or 3) for a non-negligible fraction of signatures.
+//! Both [`ecdsa::SigningKey::sign_prehash_recoverable`] and
+//! [`ecdsa::VerifyingKey::recover_from_prehash`] handle this case;
+//! the latter restores `R.x = r + n` and fails if that is not a valid x-coordinate.
+
+use ecdsa::{hazmat::VerifyPrimitive, RecoveryId, Signature, SignatureSize, VerifyingKey};
+use primeorder::elliptic_curve::{
+    generic_array::ArrayLength,
+    point::DecompressPoint,
+    sec1::{FromEncodedPoint, ModulusSize, ToEncodedPoint},
+    AffinePoint, CurveArithmetic, FieldBytesSize, PrimeCurve,
+};
+
+/// Returns the public keys recovered from the signature for every recovery id
+/// (the element `i` corresponds to the recovery id `i`), or `None` where the recovery fails.
+///
+/// Every returned key verifies the signature; the one used for signing
+/// is at the position of the recovery id returned along with the signature.
+pub fn candidate_public_keys<C>(
+    prehash: &[u8],
+    signature: &Signature<C>,
+) -> [Option<VerifyingKey<C>>; 4]
+where
+    C: PrimeCurve + CurveArithmetic,
+    AffinePoint<C>:
+        DecompressPoint<C> + FromEncodedPoint<C> + ToEncodedPoint<C> + VerifyPrimitive<C>,
+    FieldBytesSize<C>: ModulusSize,
+    SignatureSize<C>: ArrayLength<u8>,
+{
+    core::array::from_fn(|id| {
+        let recovery_id = RecoveryId::from_byte(id as u8).expect("the id is within range");
+        VerifyingKey::recover_from_prehash(prehash, signature, recovery_id).ok()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use ecdsa::{
+        hazmat::DigestPrimitive, signature::hazmat::PrehashVerifier, SigningKey, VerifyingKey,
+    };
+    use rand_core::OsRng;
+    use sha2::Digest;
+
+    use super::candidate_public_keys;
+    use crate::{
+        witness::{EcdsaWitness, KnownWitnesses},
+        TinyCurve16, TinyCurve32, TinyCurve64,
+    };
+
+    macro_rules! check_all_ids {
+        ($curve:ident, $module:ident, $sk:expr, $prehash:expr) => {{
+            let sk: &SigningKey<$curve> = $sk;
+            let prehash: &[u8] = $prehash;
+            let vk = VerifyingKey::from(sk);
+
+            let (signature, recovery_id) = sk.sign_prehash_recoverable(prehash).unwrap();
+            let candidates = candidate_public_keys(prehash, &signature);
+
+            // The signing key is recovered with the returned id
+            assert_eq!(candidates[recovery_id.to_byte() as usize], Some(vk));
+            assert_eq!(
+                VerifyingKey::recover_from_prehash(prehash, &signature, recovery_id).unwrap(),
+                vk
+            );
+
+            // With a reduced x-coordinate, `R.x = r + n` must still be a field element
+            let r = u128::from(signature.r().to_u64());
+            let r_plus_order_in_field =
+                r + u128::from(crate::$module::ORDER) < u128::from(crate::$module::FIELD_MODULUS);
+
+            for (id, candidate) in candidates.iter().enumerate() {
+                let is_x_reduced = id & 2 != 0;
+                if is_x_reduced && !r_plus_order_in_field {
+                    assert!(candidate.is_none());
+                }
+                if let Some(candidate) = candidate {
+                    assert!(candidate.verify_prehash(prehash, &signature).is_ok());
+                    // The keys for the ids with the same reduction flag but a different parity
+                    // correspond to `R` and `-R`, so they cannot be equal.
+                    assert_ne!(Some(*candidate), candidates[id ^ 1]);
+                }
+                if id as u8 != recovery_id.to_byte() {
+                    assert_ne!(*candidate, Some(vk));
+                }
+            }
+
+            recovery_id
+        }};
+    }
+
+    #[test]
+    fn all_ids_random() {
+        let sk = SigningKey::<TinyCurve16>::random(&mut OsRng);
+        let mut seen_ids = [false; 4];
+        // About 0.4% of signatures have a reduced x-coordinate on TinyCurve16
+        for i in 0u32..4096 {
+            let prehash = <TinyCurve16 as DigestPrimitive>::Digest::digest(i.to_be_bytes());
+            // Signing fails with a probability of about `3/n`: if `r = 0`
+            // (`R.x = n` is possible since `p > n`) or if `s = 0`.
+            if sk.sign_prehash_recoverable(&prehash).is_err() {
+                continue;
+            }
+            let recovery_id = check_all_ids!(TinyCurve16, curve16, &sk, &prehash);
+            seen_ids[recovery_id.to_byte() as usize] = true;
+        }
+        assert!(seen_ids[0] && seen_ids[1] && (seen_ids[2] || seen_ids[3]));
+
+        let sk = SigningKey::<TinyCurve32>::random(&mut OsRng);
+        let prehash = b"123456781234567812345678";
+        check_all_ids!(TinyCurve32, curve32, &sk, prehash);
+
+        let sk = SigningKey::<TinyCurve64>::random(&mut OsRng);
+        check_all_ids!(TinyCurve64, curve64, &sk, prehash);
+    }
+
+    fn witness_prehash<C: DigestPrimitive>(
+        witness: &EcdsaWitness,
+    ) -> sha2::digest::Output<C::Digest> {
+        C::Digest::digest(witness.message)
+    }
+
+    #[test]
+    fn all_ids_reduced_x() {
+        let witness = TinyCurve16::ECDSA_X_OVERFLOW.unwrap();
+        let prehash = witness_prehash::<TinyCurve16>(&witness);
+        let recovery_id = check_all_ids!(TinyCurve16, curve16, &witness.signing_key(), &prehash);
+        assert!(recovery_id.is_x_reduced());
+
+        let witness = TinyCurve32::ECDSA_X_OVERFLOW.unwrap();
+        let prehash = witness_prehash::<TinyCurve32>(&witness);
+        let recovery_id = check_all_ids!(TinyCurve32, curve32, &witness.signing_key(), &prehash);
+        assert!(recovery_id.is_x_reduced());
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn reduced_x_exhaustive() {
+        use ecdsa::Signature;
+        use primeorder::elliptic_curve::Field;
+
+        use crate::{curve16, FieldElement16, FieldVec, Scalar16};
+
+        // For every `r` such that `r + n < p`, the keys with a reduced x-coordinate
+        // are recovered if and only if `r + n` is an x-coordinate of a point on the curve.
+        let rs = 1..curve16::FIELD_MODULUS - curve16::ORDER;
+        let xs = rs
+            .clone()
+            .map(|r| FieldElement16::from(r + curve16::ORDER))
+            .collect::<FieldVec<u16, { curve16::FIELD_MODULUS }>>();
+        let rhs = xs.curve_equation_rhs::<TinyCurve16>();
+
+        let prehash = [0x5a; 32];
+        for (r, y2) in rs.zip(rhs.iter()) {
+            let signature =
+                Signature::<TinyCurve16>::from_scalars(Scalar16::from(r), Scalar16::ONE).unwrap();
+            let candidates = candidate_public_keys(&prehash, &signature);
+            let is_x = bool::from(y2.sqrt().is_some());
+            assert_eq!(candidates[2].is_some(), is_x);
+            assert_eq!(candidates[3].is_some(), is_x);
+        }
+    }
+
+    #[test]
+    fn all_ids_zero_hash() {
+        let witness = TinyCurve16::ECDSA_ZERO_HASH.unwrap();
+        let prehash = witness_prehash::<TinyCurve16>(&witness);
+        check_all_ids!(TinyCurve16, curve16, &witness.signing_key(), &prehash);
+    }
+}
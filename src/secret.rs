@@ -0,0 +1,502 @@
+//! Tracking of secret values reaching variable-time primitives.
+//!
+//! Some of the arithmetic in this crate is not constant-time
+//! (which is fine for test curves, but may hide issues in the code using them).
+//! [`SecretScalar`] wraps a scalar and records which of these primitives
+//! it (or any value derived from it) has passed through.
+//!
+//! With the `ct-audit` feature, the primitives themselves report to [`audit`],
+//! so the secrets passing through the code of other crates (e.g. `ecdsa` or `primeorder`)
+//! are covered as well.
+
+#[cfg(feature = "ct-audit")]
+use core::cell::Cell;
+use core::{
+    fmt,
+    ops::{Add, BitOr, BitOrAssign, Mul, Neg, Sub},
+};
+
+use primeorder::{
+    elliptic_curve::{ops::Invert, subtle::CtOption, CurveArithmetic, Field},
+    PrimeCurveParams, ProjectivePoint,
+};
+
+/// A set of variable-time primitives.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct VartimeOps(u8);
+
+impl VartimeOps {
+    /// No variable-time primitives.
+    pub const NONE: Self = Self(0);
+
+    /// Modular inversion with the extended Euclidean algorithm
+    /// (used in the explicitly variable-time inversion).
+    pub const INVERSION: Self = Self(1 << 0);
+
+    /// Exponentiation taking time dependent on the exponent
+    /// (used in [`Field::pow_vartime`]; the exponentiations with public exponents
+    /// performed by the constant-time inversion and the square root are not included).
+    pub const EXPONENTIATION: Self = Self(1 << 1);
+
+    /// Returns `true` if the set is empty.
+    pub const fn is_empty(&self) -> bool {
+        self.0 == 0
+    }
+
+    /// Returns `true` if all the primitives in `other` are in this set.
+    pub const fn contains(&self, other: Self) -> bool {
+        self.0 & other.0 == other.0
+    }
+
+    pub(crate) const fn union(self, other: Self) -> Self {
+        Self(self.0 | other.0)
+    }
+}
+
+impl BitOr for VartimeOps {
+    type Output = Self;
+    fn bitor(self, rhs: Self) -> Self::Output {
+        self.union(rhs)
+    }
+}
+
+impl BitOrAssign for VartimeOps {
+    fn bitor_assign(&mut self, rhs: Self) {
+        *self = self.union(rhs)
+    }
+}
+
+/// The reaction to a variable-time primitive reached within [`audit`].
+#[cfg(feature = "ct-audit")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AuditMode {
+    /// Record the primitive, to be returned from [`audit`].
+    Record,
+    /// Panic.
+    Panic,
+}
+
+#[cfg(feature = "ct-audit")]
+std::thread_local! {
+    static MODE: Cell<Option<AuditMode>> = const { Cell::new(None) };
+    static REACHED: Cell<VartimeOps> = const { Cell::new(VartimeOps::NONE) };
+}
+
+/// Reports that the current thread reached the variable-time primitives `ops`.
+///
+/// Does nothing outside of [`audit`] or without the `ct-audit` feature.
+#[cfg_attr(not(feature = "ct-audit"), allow(unused_variables))]
+pub(crate) fn reached(ops: VartimeOps) {
+    #[cfg(feature = "ct-audit")]
+    match MODE.with(Cell::get) {
+        None => {}
+        Some(AuditMode::Record) => REACHED.with(|reached| reached.set(reached.get() | ops)),
+        Some(AuditMode::Panic) => panic_on_violation(ops),
+    }
+}
+
+/// Runs `f`, treating all the values it operates on as secret,
+/// and returns its result along with the variable-time primitives it reached
+/// in the current thread.
+///
+/// With [`AuditMode::Panic`], panics as soon as a variable-time primitive is reached instead.
+/// The calls can be nested; the outer call includes the primitives reached in the inner ones.
+#[cfg(feature = "ct-audit")]
+pub fn audit<R>(mode: AuditMode, f: impl FnOnce() -> R) -> (R, VartimeOps) {
+    let guard = AuditGuard::enter(mode);
+    let result = f();
+    let inner_reached = guard.exit();
+    if !inner_reached.is_empty() {
+        reached(inner_reached);
+    }
+    (result, inner_reached)
+}
+
+/// Restores the state of the outer [`audit`] call when dropped,
+/// so that a panic in the audited code does not leave the thread in the audit mode.
+#[cfg(feature = "ct-audit")]
+struct AuditGuard {
+    outer_mode: Option<AuditMode>,
+    outer_reached: VartimeOps,
+}
+
+#[cfg(feature = "ct-audit")]
+impl AuditGuard {
+    fn enter(mode: AuditMode) -> Self {
+        Self {
+            outer_mode: MODE.with(|m| m.replace(Some(mode))),
+            outer_reached: REACHED.with(|reached| reached.replace(VartimeOps::NONE)),
+        }
+    }
+
+    /// Returns the primitives reached since [`Self::enter`]
+    /// (the outer state is restored when `self` is dropped).
+    fn exit(self) -> VartimeOps {
+        REACHED.with(Cell::get)
+    }
+}
+
+#[cfg(feature = "ct-audit")]
+impl Drop for AuditGuard {
+    fn drop(&mut self) {
+        MODE.with(|m| m.set(self.outer_mode));
+        REACHED.with(|reached| reached.set(self.outer_reached));
+    }
+}
+
+/// The variable-time primitives reached by the operations of a field element.
+pub trait VartimeInfo {
+    /// Multiplication and squaring.
+    const MUL: VartimeOps;
+    /// Inversion.
+    const INVERT: VartimeOps;
+    /// Variable-time inversion.
+    const INVERT_VARTIME: VartimeOps;
+    /// Square root.
+    const SQRT: VartimeOps;
+}
+
+/// A scalar of the curve `C` that records the variable-time primitives it reaches.
+///
+/// The violations are propagated to the values derived from it,
+/// and can be checked with [`violations`](`Self::violations`).
+/// A scalar created with [`new_strict`](`Self::new_strict`) panics on the first violation instead.
+///
+/// Public values can be mixed in by wrapping them with [`new`](`Self::new`).
+///
+/// The [`Debug`](fmt::Debug) output only contains the violations, not the value.
+#[derive(Clone, Copy)]
+pub struct SecretScalar<C: CurveArithmetic> {
+    value: C::Scalar,
+    violations: VartimeOps,
+    strict: bool,
+}
+
+impl<C> SecretScalar<C>
+where
+    C: PrimeCurveParams,
+    C::Scalar: VartimeInfo,
+    C::FieldElement: VartimeInfo,
+{
+    /// Wraps a secret scalar, recording the violations.
+    pub fn new(value: C::Scalar) -> Self {
+        Self {
+            value,
+            violations: VartimeOps::NONE,
+            strict: false,
+        }
+    }
+
+    /// Wraps a secret scalar, panicking on a violation.
+    pub fn new_strict(value: C::Scalar) -> Self {
+        Self {
+            value,
+            violations: VartimeOps::NONE,
+            strict: true,
+        }
+    }
+
+    /// Returns the wrapped scalar.
+    pub fn expose(&self) -> C::Scalar {
+        self.value
+    }
+
+    /// Returns the variable-time primitives this value or the values it was derived from reached.
+    pub fn violations(&self) -> VartimeOps {
+        self.violations
+    }
+
+    fn derive(&self, value: impl FnOnce() -> C::Scalar, ops: VartimeOps) -> Self {
+        let (value, ops) = track(self.strict, value, ops);
+        self.derive_tracked(value, ops)
+    }
+
+    fn derive_tracked(&self, value: C::Scalar, ops: VartimeOps) -> Self {
+        Self {
+            value,
+            violations: self.violations | ops,
+            strict: self.strict,
+        }
+    }
+
+    fn derive_option(
+        &self,
+        value: impl FnOnce() -> CtOption<C::Scalar>,
+        ops: VartimeOps,
+    ) -> CtOption<Self> {
+        let (value, ops) = track(self.strict, value, ops);
+        let result = self.derive_tracked(value.unwrap_or(C::Scalar::ZERO), ops);
+        CtOption::new(result, value.is_some())
+    }
+
+    fn combine(&self, rhs: &Self, value: impl FnOnce() -> C::Scalar, ops: VartimeOps) -> Self {
+        let strict = self.strict || rhs.strict;
+        let (value, ops) = track(strict, value, ops);
+        Self {
+            value,
+            violations: self.violations | rhs.violations | ops,
+            strict,
+        }
+    }
+
+    /// Squares the scalar.
+    pub fn square(&self) -> Self {
+        self.derive(|| self.value.square(), C::Scalar::MUL)
+    }
+
+    /// Inverts the scalar.
+    pub fn invert(&self) -> CtOption<Self> {
+        self.derive_option(|| Field::invert(&self.value), C::Scalar::INVERT)
+    }
+
+    /// Inverts the scalar using the variable-time algorithm.
+    pub fn invert_vartime(&self) -> CtOption<Self> {
+        self.derive_option(|| self.value.invert_vartime(), C::Scalar::INVERT_VARTIME)
+    }
+
+    /// Calculates the square root of the scalar.
+    pub fn sqrt(&self) -> CtOption<Self> {
+        self.derive_option(|| self.value.sqrt(), C::Scalar::SQRT)
+    }
+
+    /// Multiplies a point by the scalar.
+    ///
+    /// The result is not tracked, but the violations are recorded in `self`.
+    pub fn mul_point(&mut self, point: &ProjectivePoint<C>) -> ProjectivePoint<C> {
+        let (result, ops) = track(self.strict, || *point * self.value, C::FieldElement::MUL);
+        self.violations |= ops;
+        result
+    }
+}
+
+impl<C: CurveArithmetic> fmt::Debug for SecretScalar<C> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SecretScalar")
+            .field("violations", &self.violations)
+            .field("strict", &self.strict)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Runs `f` and returns its result along with the variable-time primitives it reached:
+/// `ops`, and (with the `ct-audit` feature) the ones reported by the primitives themselves.
+///
+/// If `strict` is set, panics if any were reached.
+fn track<R>(strict: bool, f: impl FnOnce() -> R, ops: VartimeOps) -> (R, VartimeOps) {
+    #[cfg(feature = "ct-audit")]
+    let (result, ops) = {
+        let mode = if strict {
+            AuditMode::Panic
+        } else {
+            AuditMode::Record
+        };
+        let (result, reached) = audit(mode, f);
+        (result, ops | reached)
+    };
+    #[cfg(not(feature = "ct-audit"))]
+    let result = f();
+
+    check(strict, ops);
+    (result, ops)
+}
+
+fn check(strict: bool, ops: VartimeOps) {
+    if strict && !ops.is_empty() {
+        panic_on_violation(ops);
+    }
+}
+
+fn panic_on_violation(ops: VartimeOps) -> ! {
+    panic!("A secret value reached variable-time primitives: {ops:?}");
+}
+
+impl<C> Add<Self> for SecretScalar<C>
+where
+    C: PrimeCurveParams,
+    C::Scalar: VartimeInfo,
+    C::FieldElement: VartimeInfo,
+{
+    type Output = Self;
+    fn add(self, rhs: Self) -> Self::Output {
+        self.combine(&rhs, || self.value + rhs.value, VartimeOps::NONE)
+    }
+}
+
+impl<C> Sub<Self> for SecretScalar<C>
+where
+    C: PrimeCurveParams,
+    C::Scalar: VartimeInfo,
+    C::FieldElement: VartimeInfo,
+{
+    type Output = Self;
+    fn sub(self, rhs: Self) -> Self::Output {
+        self.combine(&rhs, || self.value - rhs.value, VartimeOps::NONE)
+    }
+}
+
+impl<C> Mul<Self> for SecretScalar<C>
+where
+    C: PrimeCurveParams,
+    C::Scalar: VartimeInfo,
+    C::FieldElement: VartimeInfo,
+{
+    type Output = Self;
+    fn mul(self, rhs: Self) -> Self::Output {
+        self.combine(&rhs, || self.value * rhs.value, C::Scalar::MUL)
+    }
+}
+
+impl<C> Neg for SecretScalar<C>
+where
+    C: PrimeCurveParams,
+    C::Scalar: VartimeInfo,
+    C::FieldElement: VartimeInfo,
+{
+    type Output = Self;
+    fn neg(self) -> Self::Output {
+        self.derive(|| -self.value, VartimeOps::NONE)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use primeorder::{
+        elliptic_curve::{group::Group, CurveArithmetic, Field},
+        ProjectivePoint,
+    };
+    use rand_core::OsRng;
+
+    use super::{SecretScalar, VartimeInfo};
+    use crate::{TinyCurve16, TinyCurve64};
+
+    #[test]
+    fn propagation() {
+        type S = <TinyCurve64 as CurveArithmetic>::Scalar;
+        let x = SecretScalar::<TinyCurve64>::new(S::random(&mut OsRng));
+        let y = SecretScalar::<TinyCurve64>::new(S::random(&mut OsRng));
+
+        let z = x + y - SecretScalar::new(S::ONE);
+        assert!(z.violations().is_empty());
+
+        let z_inv = z.invert().unwrap();
+        assert!(z_inv.violations().is_empty());
+        assert_eq!(z_inv.expose() * z.expose(), S::ONE);
+
+        let z_inv = z.invert_vartime().unwrap();
+        assert_eq!(z_inv.violations(), S::INVERT_VARTIME);
+        assert_eq!((z_inv * x).violations(), S::INVERT_VARTIME | S::MUL);
+        assert_eq!(z_inv.expose() * z.expose(), S::ONE);
+    }
+
+    #[test]
+    fn constant_time_ops() {
+        type S = <TinyCurve16 as CurveArithmetic>::Scalar;
+        let mut x = SecretScalar::<TinyCurve16>::new_strict(S::random(&mut OsRng));
+        let y = x.square() * x;
+        let _ = y.invert();
+        let _ = y.sqrt();
+
+        let p = x.mul_point(&ProjectivePoint::<TinyCurve16>::generator());
+        assert_eq!(p, ProjectivePoint::<TinyCurve16>::generator() * x.expose());
+        assert!(x.violations().is_empty());
+    }
+
+    #[test]
+    fn debug_hides_value() {
+        type S = <TinyCurve64 as CurveArithmetic>::Scalar;
+        let x = SecretScalar::<TinyCurve64>::new(S::from(0x1234567u64));
+        assert_eq!(
+            format!("{:?}", x.invert_vartime().unwrap()),
+            "SecretScalar { violations: VartimeOps(1), strict: false, .. }"
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "variable-time primitives")]
+    fn strict() {
+        type S = <TinyCurve64 as CurveArithmetic>::Scalar;
+        let x = SecretScalar::<TinyCurve64>::new_strict(S::random(&mut OsRng));
+        let _ = x.invert_vartime();
+    }
+}
+
+#[cfg(all(test, feature = "ct-audit"))]
+mod tests_audit {
+    use primeorder::elliptic_curve::{CurveArithmetic, Field};
+    use rand_core::OsRng;
+
+    use super::{audit, AuditMode, VartimeOps};
+    use crate::TinyCurve32;
+
+    type S = <TinyCurve32 as CurveArithmetic>::Scalar;
+
+    #[test]
+    fn record() {
+        let x = S::random(&mut OsRng);
+
+        let (_, reached) = audit(AuditMode::Record, || (x.invert(), x.sqrt(), x.pow([3])));
+        assert!(reached.is_empty());
+
+        let (_, reached) = audit(AuditMode::Record, || x.invert_vartime());
+        assert_eq!(reached, VartimeOps::INVERSION);
+
+        let (_, reached) = audit(AuditMode::Record, || {
+            let (_, inner) = audit(AuditMode::Record, || x.pow_vartime([3]));
+            assert_eq!(inner, VartimeOps::EXPONENTIATION);
+            x.invert_vartime()
+        });
+        assert_eq!(reached, VartimeOps::INVERSION | VartimeOps::EXPONENTIATION);
+
+        // Nothing is reported outside of `audit()`
+        let _ = x.invert_vartime();
+        let (_, reached) = audit(AuditMode::Record, || x.square());
+        assert!(reached.is_empty());
+    }
+
+    #[test]
+    #[should_panic(expected = "variable-time primitives")]
+    fn panic() {
+        let x = S::random(&mut OsRng);
+        let _ = audit(AuditMode::Panic, || x.pow_vartime([3]));
+    }
+
+    #[test]
+    fn panic_restores_mode() {
+        let x = S::random(&mut OsRng);
+        let (_, reached) = audit(AuditMode::Record, || {
+            let result =
+                std::panic::catch_unwind(|| audit(AuditMode::Panic, || x.pow_vartime([3])));
+            assert!(result.is_err());
+            // Back in the recording mode of the outer call
+            x.invert_vartime()
+        });
+        assert_eq!(reached, VartimeOps::INVERSION);
+
+        // Not in the audit mode after the outer call
+        let _ = x.invert_vartime();
+        let (_, reached) = audit(AuditMode::Record, || x.square());
+        assert!(reached.is_empty());
+    }
+
+    #[cfg(feature = "ecdsa")]
+    #[test]
+    fn ecdsa() {
+        use ecdsa::{
+            signature::{Signer, Verifier},
+            Signature, SigningKey,
+        };
+
+        let sk = SigningKey::<TinyCurve32>::random(&mut OsRng);
+        let (signature, reached) = audit(AuditMode::Panic, || -> Signature<TinyCurve32> {
+            sk.sign(b"message")
+        });
+        assert!(reached.is_empty());
+
+        // Verification inverts `s` in variable time (which is fine since it is public)
+        let (result, reached) = audit(AuditMode::Record, || {
+            sk.verifying_key().verify(b"message", &signature)
+        });
+        assert!(result.is_ok());
+        assert_eq!(reached, VartimeOps::INVERSION);
+    }
+}
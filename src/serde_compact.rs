@@ -0,0 +1,240 @@
+//! `serde` support using the encodings from [`CompactEncoding`].
+//!
+//! Human-readable formats get a lowercase hex string, and binary formats get the raw bytes.
+//!
+//! The field elements, [`Point`] and the BIP32 newtypes from this crate
+//! implement `Serialize` and `Deserialize` directly.
+//! The types from other crates that implement [`CompactEncoding`]
+//! (`AffinePoint`, `ProjectivePoint`, `SecretKey`, `PublicKey`, and `ecdsa::Signature`)
+//! can be (de)serialized with this module as `#[serde(with = "tiny_curve::serde_compact")]`.
+//! Note that some of them already have `serde` impls with a different encoding in their own crates.
+
+use primeorder::{PrimeCurveParams, ProjectivePoint};
+use serdect::serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+
+#[cfg(feature = "bip32")]
+use primeorder::elliptic_curve::{CurveArithmetic, PublicKey, SecretKey};
+
+use crate::{prime_field::FieldElement, traits::PrimitiveUint, CompactEncoding, Point};
+
+#[cfg(feature = "bip32")]
+use crate::{PrivateKeyBip32, PublicKeyBip32};
+
+/// Serializes a value using its compact encoding.
+pub fn serialize<T, S>(value: &T, serializer: S) -> Result<S::Ok, S::Error>
+where
+    T: CompactEncoding,
+    S: Serializer,
+{
+    serdect::array::serialize_hex_lower_or_bin(&value.to_compact_bytes(), serializer)
+}
+
+/// Deserializes a value from its compact encoding, failing if it is not canonical.
+pub fn deserialize<'de, T, D>(deserializer: D) -> Result<T, D::Error>
+where
+    T: CompactEncoding,
+    D: Deserializer<'de>,
+{
+    let mut bytes = T::Bytes::default();
+    serdect::array::deserialize_hex_or_bin(bytes.as_mut(), deserializer)?;
+    Option::<T>::from(T::from_compact_bytes(&bytes))
+        .ok_or_else(|| de::Error::custom("invalid compact encoding"))
+}
+
+impl<T, const M: u64> Serialize for FieldElement<T, M>
+where
+    T: PrimitiveUint,
+    Self: CompactEncoding,
+{
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serialize(self, serializer)
+    }
+}
+
+impl<'de, T, const M: u64> Deserialize<'de> for FieldElement<T, M>
+where
+    T: PrimitiveUint,
+    Self: CompactEncoding,
+{
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserialize(deserializer)
+    }
+}
+
+impl<C> Serialize for Point<C>
+where
+    C: PrimeCurveParams,
+    ProjectivePoint<C>: CompactEncoding,
+{
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serialize(self.as_ref(), serializer)
+    }
+}
+
+impl<'de, C> Deserialize<'de> for Point<C>
+where
+    C: PrimeCurveParams,
+    ProjectivePoint<C>: CompactEncoding,
+{
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserialize::<ProjectivePoint<C>, D>(deserializer).map(Self::from)
+    }
+}
+
+#[cfg(feature = "bip32")]
+impl<C> Serialize for PrivateKeyBip32<C>
+where
+    C: CurveArithmetic,
+    SecretKey<C>: CompactEncoding,
+{
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serialize(self.as_ref(), serializer)
+    }
+}
+
+#[cfg(feature = "bip32")]
+impl<'de, C> Deserialize<'de> for PrivateKeyBip32<C>
+where
+    C: CurveArithmetic,
+    SecretKey<C>: CompactEncoding,
+{
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserialize::<SecretKey<C>, D>(deserializer).map(Self::from)
+    }
+}
+
+#[cfg(feature = "bip32")]
+impl<C> Serialize for PublicKeyBip32<C>
+where
+    C: CurveArithmetic,
+    PublicKey<C>: CompactEncoding,
+{
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serialize(self.as_ref(), serializer)
+    }
+}
+
+#[cfg(feature = "bip32")]
+impl<'de, C> Deserialize<'de> for PublicKeyBip32<C>
+where
+    C: CurveArithmetic,
+    PublicKey<C>: CompactEncoding,
+{
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserialize::<PublicKey<C>, D>(deserializer).map(Self::from)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use primeorder::{
+        elliptic_curve::{Field, PublicKey, SecretKey},
+        AffinePoint,
+    };
+    use rand_core::OsRng;
+    use serde::{de::DeserializeOwned, Serialize};
+
+    use super::{deserialize, serialize};
+    use crate::{
+        CompactEncoding, FieldElement32, Point16, Point64, Scalar16, Scalar64, TinyCurve16,
+        TinyCurve32,
+    };
+
+    fn to_json_with<T: CompactEncoding>(value: &T) -> String {
+        let mut buffer = Vec::new();
+        serialize(value, &mut serde_json::Serializer::new(&mut buffer)).unwrap();
+        String::from_utf8(buffer).unwrap()
+    }
+
+    fn from_json_with<T: CompactEncoding>(json: &str) -> Option<T> {
+        deserialize(&mut serde_json::Deserializer::from_str(json)).ok()
+    }
+
+    fn check_roundtrip<T>(value: T, binary_size: usize)
+    where
+        T: Serialize + DeserializeOwned + PartialEq + core::fmt::Debug,
+    {
+        let json = serde_json::to_string(&value).unwrap();
+        assert_eq!(json.len(), binary_size * 2 + 2);
+        assert_eq!(serde_json::from_str::<T>(&json).unwrap(), value);
+
+        let bytes = postcard::to_allocvec(&value).unwrap();
+        assert_eq!(bytes.len(), binary_size);
+        assert_eq!(postcard::from_bytes::<T>(&bytes).unwrap(), value);
+    }
+
+    #[test]
+    fn own_types() {
+        assert_eq!(
+            serde_json::to_string(&Scalar16::from(0xabcu64)).unwrap(),
+            "\"0abc\""
+        );
+        assert_eq!(
+            postcard::to_allocvec(&Scalar16::from(0xabcu64)).unwrap(),
+            [0x0a, 0xbc]
+        );
+
+        check_roundtrip(Scalar16::random(&mut OsRng), 2);
+        check_roundtrip(FieldElement32::random(&mut OsRng), 4);
+        check_roundtrip(Scalar64::random(&mut OsRng), 8);
+        check_roundtrip(Point16::GENERATOR.mul_u64(12345), 3);
+        check_roundtrip(Point64::GENERATOR.mul_u64(12345), 9);
+        check_roundtrip(Point16::IDENTITY, 3);
+
+        // Non-canonical values and wrong lengths
+        assert!(serde_json::from_str::<Scalar16>("\"fe93\"").is_err());
+        assert!(serde_json::from_str::<Scalar16>("\"000abc\"").is_err());
+        assert!(serde_json::from_str::<Point16>("\"040000\"").is_err());
+        assert!(postcard::from_bytes::<Scalar16>(&[0xfe, 0x93]).is_err());
+        assert!(postcard::from_bytes::<Scalar16>(&[0x0a]).is_err());
+    }
+
+    #[test]
+    fn foreign_types() {
+        let secret_key = SecretKey::<TinyCurve16>::random(&mut OsRng);
+        let json = to_json_with(&secret_key);
+        assert_eq!(json.len(), 2 * 2 + 2);
+        assert_eq!(from_json_with(&json), Some(secret_key.clone()));
+        assert_eq!(from_json_with::<SecretKey<TinyCurve16>>("\"0000\""), None);
+
+        let public_key = secret_key.public_key();
+        let json = to_json_with(&public_key);
+        assert_eq!(json.len(), 3 * 2 + 2);
+        assert_eq!(from_json_with(&json), Some(public_key));
+        assert_eq!(from_json_with::<PublicKey<TinyCurve16>>("\"000000\""), None);
+
+        let point = *public_key.as_affine();
+        assert_eq!(from_json_with(&to_json_with(&point)), Some(point));
+        assert_eq!(
+            from_json_with(&to_json_with(&AffinePoint::<TinyCurve32>::IDENTITY)),
+            Some(AffinePoint::<TinyCurve32>::IDENTITY)
+        );
+    }
+
+    #[cfg(feature = "ecdsa")]
+    #[test]
+    fn signature() {
+        use ecdsa::{signature::Signer, Signature, SigningKey};
+
+        let signing_key = SigningKey::<TinyCurve32>::random(&mut OsRng);
+        let signature: Signature<TinyCurve32> = signing_key.sign(b"message");
+        let json = to_json_with(&signature);
+        assert_eq!(json.len(), 8 * 2 + 2);
+        assert_eq!(from_json_with(&json), Some(signature));
+        assert_eq!(
+            from_json_with::<Signature<TinyCurve32>>("\"0000000000000001\""),
+            None
+        );
+    }
+
+    #[cfg(feature = "bip32")]
+    #[test]
+    fn bip32() {
+        use crate::{PrivateKeyBip32, PublicKeyBip32};
+
+        let secret_key = SecretKey::<TinyCurve16>::random(&mut OsRng);
+        let public_key = secret_key.public_key();
+        check_roundtrip(PrivateKeyBip32::from(secret_key), 2);
+        check_roundtrip(PublicKeyBip32::from(public_key), 3);
+    }
+}
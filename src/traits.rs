@@ -5,7 +5,15 @@ use num_traits::{
 };
 use primeorder::elliptic_curve::subtle::{ConditionallySelectable, ConstantTimeEq};
 
-use crate::reciprocal::{rem_wide_with_reciprocal, Reciprocal};
+#[cfg(all(feature = "alloc", not(feature = "montgomery16")))]
+use crate::barrett::{rem_with_barrett_lanes, BarrettLanes};
+#[cfg(all(feature = "alloc", not(feature = "montgomery32")))]
+use crate::barrett::{rem_with_barrett_wide_lanes, BarrettWideLanes};
+use crate::{
+    barrett::{rem_with_barrett, Barrett},
+    montgomery::Montgomery,
+    reciprocal::{rem_wide_with_reciprocal, Reciprocal},
+};
 
 pub trait PrimeFieldConstants<T> {
     type Repr: AsRef<[u8]> + AsMut<[u8]> + Send + Sync + Default + Clone + Copy;
@@ -21,8 +29,74 @@ pub trait PrimeFieldConstants<T> {
     const DELTA: T;
 }
 
+/// The constants from [`PrimeFieldConstants`] in the internal representation of field elements
+/// (which may differ from the canonical one, see [`PrimitiveUint::to_internal`]).
+pub trait InternalConstants<T> {
+    const ONE: T;
+    const TWO_INV: T;
+    const MULTIPLICATIVE_GENERATOR: T;
+    const ROOT_OF_UNITY: T;
+    const ROOT_OF_UNITY_INV: T;
+    const DELTA: T;
+}
+
+/// Converts a value in `[0, modulus)` to the internal representation
+/// for a field based on a `bits`-sized integer type (usable in constant context).
+pub(crate) const fn to_internal_const(value: u64, modulus: u64, bits: u32) -> u64 {
+    let montgomery = (bits == 16 && cfg!(feature = "montgomery16"))
+        || (bits == 32 && cfg!(feature = "montgomery32"))
+        || (bits == 64 && cfg!(feature = "montgomery64"));
+    if montgomery {
+        Montgomery::new(modulus, bits).encode(value)
+    } else {
+        value
+    }
+}
+
+macro_rules! impl_internal_constants {
+    ($t:ty) => {
+        // The casts are trivial for `u64`.
+        #[allow(trivial_numeric_casts)]
+        impl<const M: u64> InternalConstants<$t> for Modulus<$t, M>
+        where
+            Self: PrimeFieldConstants<$t>,
+        {
+            const ONE: $t = to_internal_const(1, M, <$t>::BITS) as $t;
+            const TWO_INV: $t = to_internal_const(
+                <Self as PrimeFieldConstants<$t>>::TWO_INV as u64,
+                M,
+                <$t>::BITS,
+            ) as $t;
+            const MULTIPLICATIVE_GENERATOR: $t = to_internal_const(
+                <Self as PrimeFieldConstants<$t>>::MULTIPLICATIVE_GENERATOR as u64,
+                M,
+                <$t>::BITS,
+            ) as $t;
+            const ROOT_OF_UNITY: $t = to_internal_const(
+                <Self as PrimeFieldConstants<$t>>::ROOT_OF_UNITY as u64,
+                M,
+                <$t>::BITS,
+            ) as $t;
+            const ROOT_OF_UNITY_INV: $t = to_internal_const(
+                <Self as PrimeFieldConstants<$t>>::ROOT_OF_UNITY_INV as u64,
+                M,
+                <$t>::BITS,
+            ) as $t;
+            const DELTA: $t = to_internal_const(
+                <Self as PrimeFieldConstants<$t>>::DELTA as u64,
+                M,
+                <$t>::BITS,
+            ) as $t;
+        }
+    };
+}
+
 pub struct Modulus<T, const M: u64>(PhantomData<T>);
 
+impl_internal_constants!(u16);
+impl_internal_constants!(u32);
+impl_internal_constants!(u64);
+
 pub trait HasReciprocal {
     const RECIPROCAL: Reciprocal;
 }
@@ -31,6 +105,56 @@ impl<const M: u64> HasReciprocal for Modulus<u64, M> {
     const RECIPROCAL: Reciprocal = Reciprocal::new(M);
 }
 
+#[cfg(any(
+    feature = "montgomery16",
+    feature = "montgomery32",
+    feature = "montgomery64"
+))]
+pub trait HasMontgomery {
+    const MONTGOMERY: Montgomery;
+}
+
+#[cfg(any(
+    feature = "montgomery16",
+    feature = "montgomery32",
+    feature = "montgomery64"
+))]
+impl<T, const M: u64> HasMontgomery for Modulus<T, M> {
+    const MONTGOMERY: Montgomery = Montgomery::new(M, (size_of::<T>() * 8) as u32);
+}
+
+pub trait HasBarrett {
+    const BARRETT: Barrett;
+}
+
+impl<const M: u64> HasBarrett for Modulus<u16, M> {
+    const BARRETT: Barrett = Barrett::new(M);
+}
+
+impl<const M: u64> HasBarrett for Modulus<u32, M> {
+    const BARRETT: Barrett = Barrett::new(M);
+}
+
+#[cfg(all(feature = "alloc", not(feature = "montgomery16")))]
+pub trait HasBarrettLanes {
+    const BARRETT_LANES: BarrettLanes;
+}
+
+#[cfg(all(feature = "alloc", not(feature = "montgomery16")))]
+impl<const M: u64> HasBarrettLanes for Modulus<u16, M> {
+    const BARRETT_LANES: BarrettLanes = BarrettLanes::new(M);
+}
+
+#[cfg(all(feature = "alloc", not(feature = "montgomery32")))]
+pub trait HasBarrettWideLanes {
+    const BARRETT_WIDE_LANES: BarrettWideLanes;
+}
+
+#[cfg(all(feature = "alloc", not(feature = "montgomery32")))]
+impl<const M: u64> HasBarrettWideLanes for Modulus<u32, M> {
+    const BARRETT_WIDE_LANES: BarrettWideLanes = BarrettWideLanes::new(M);
+}
+
 pub trait HasWide: Sized {
     type Wide: WideUint;
     fn to_wide(self) -> Self::Wide;
@@ -89,31 +213,148 @@ pub trait PrimitiveUint:
     + HasWide
     + Into<u64>
 {
-    fn reduce_from_wide<const M: u64>(value: Self::Wide) -> Self {
-        Self::from_wide_unchecked(
-            value
-                % Self::from_u64(M)
-                    .expect("modulus is within range")
-                    .to_wide(),
-        )
+    /// Reduces a product of two numbers below `M` modulo `M` (in constant time).
+    fn reduce_from_wide<const M: u64>(value: Self::Wide) -> Self;
+
+    /// Converts a value in `[0, M)` to the internal representation of field elements.
+    fn to_internal<const M: u64>(self) -> Self {
+        self
+    }
+
+    /// Converts a value in the internal representation of field elements to `[0, M)`.
+    fn to_canonical<const M: u64>(self) -> Self {
+        self
+    }
+
+    /// Multiplies two values in the internal representation modulo `M`.
+    fn mul_internal<const M: u64>(lhs: &Self, rhs: &Self) -> Self {
+        Self::reduce_from_wide::<M>(lhs.to_wide() * rhs.to_wide())
     }
 }
 
+/// The integer types whose element-wise field multiplication can be auto-vectorized.
+///
+/// Implemented for `u16` (the products fit into 32 bits, and the reduction is done
+/// in 32-bit lanes) and `u32` (the reduction only needs 32x32-bit products, done in 64-bit lanes).
+/// The 64-bit fields need 128-bit products for the reduction,
+/// and a loop over them is not any faster than over the field elements.
+#[cfg(feature = "alloc")]
+pub trait LaneUint: PrimitiveUint {
+    /// Multiplies `lhs` by `rhs` element-wise (in the internal representation) modulo `M`.
+    fn mul_slice<const M: u64>(lhs: &mut [Self], rhs: &[Self]) {
+        for (lhs, rhs) in lhs.iter_mut().zip(rhs) {
+            *lhs = Self::mul_internal::<M>(lhs, rhs);
+        }
+    }
+
+    /// Squares `values` element-wise (in the internal representation) modulo `M`.
+    fn square_slice<const M: u64>(values: &mut [Self]) {
+        for value in values.iter_mut() {
+            *value = Self::mul_internal::<M>(value, value);
+        }
+    }
+}
+
+/// Overrides the representation-dependent methods of [`PrimitiveUint`] to use the Montgomery form.
+#[cfg(any(
+    feature = "montgomery16",
+    feature = "montgomery32",
+    feature = "montgomery64"
+))]
+macro_rules! impl_montgomery {
+    () => {
+        #[allow(trivial_numeric_casts)]
+        fn to_internal<const M: u64>(self) -> Self {
+            Modulus::<Self, M>::MONTGOMERY.encode(self.into()) as Self
+        }
+
+        #[allow(trivial_numeric_casts)]
+        fn to_canonical<const M: u64>(self) -> Self {
+            Modulus::<Self, M>::MONTGOMERY.decode(self.into()) as Self
+        }
+
+        #[allow(trivial_numeric_casts)]
+        fn mul_internal<const M: u64>(lhs: &Self, rhs: &Self) -> Self {
+            Modulus::<Self, M>::MONTGOMERY.mul((*lhs).into(), (*rhs).into()) as Self
+        }
+    };
+}
+
 pub trait WideUint:
     Unsigned + ToBytes<Bytes = <Self as FromBytes>::Bytes> + FromBytes<Bytes: Sized> + ConstZero
 {
 }
 
-impl PrimitiveUint for u16 {}
+impl PrimitiveUint for u16 {
+    fn reduce_from_wide<const M: u64>(value: Self::Wide) -> Self {
+        let barrett = Modulus::<Self, M>::BARRETT;
+        rem_with_barrett(value.into(), &barrett) as Self
+    }
 
-impl PrimitiveUint for u32 {}
+    #[cfg(feature = "montgomery16")]
+    impl_montgomery!();
+}
+
+// With `montgomery16` the default (scalar) implementations are used.
+#[cfg(feature = "alloc")]
+impl LaneUint for u16 {
+    #[cfg(not(feature = "montgomery16"))]
+    fn mul_slice<const M: u64>(lhs: &mut [Self], rhs: &[Self]) {
+        let barrett = Modulus::<Self, M>::BARRETT_LANES;
+        for (lhs, rhs) in lhs.iter_mut().zip(rhs) {
+            *lhs = rem_with_barrett_lanes(u32::from(*lhs) * u32::from(*rhs), &barrett) as Self;
+        }
+    }
+
+    #[cfg(not(feature = "montgomery16"))]
+    fn square_slice<const M: u64>(values: &mut [Self]) {
+        let barrett = Modulus::<Self, M>::BARRETT_LANES;
+        for value in values.iter_mut() {
+            *value =
+                rem_with_barrett_lanes(u32::from(*value) * u32::from(*value), &barrett) as Self;
+        }
+    }
+}
+
+impl PrimitiveUint for u32 {
+    fn reduce_from_wide<const M: u64>(value: Self::Wide) -> Self {
+        let barrett = Modulus::<Self, M>::BARRETT;
+        Self::from_wide_unchecked(rem_with_barrett(value, &barrett))
+    }
+
+    #[cfg(feature = "montgomery32")]
+    impl_montgomery!();
+}
+
+// With `montgomery32` the default (scalar) implementations are used.
+#[cfg(feature = "alloc")]
+impl LaneUint for u32 {
+    #[cfg(not(feature = "montgomery32"))]
+    fn mul_slice<const M: u64>(lhs: &mut [Self], rhs: &[Self]) {
+        let barrett = Modulus::<Self, M>::BARRETT_WIDE_LANES;
+        for (lhs, rhs) in lhs.iter_mut().zip(rhs) {
+            *lhs = rem_with_barrett_wide_lanes(u64::from(*lhs) * u64::from(*rhs), &barrett) as Self;
+        }
+    }
+
+    #[cfg(not(feature = "montgomery32"))]
+    fn square_slice<const M: u64>(values: &mut [Self]) {
+        let barrett = Modulus::<Self, M>::BARRETT_WIDE_LANES;
+        for value in values.iter_mut() {
+            *value = rem_with_barrett_wide_lanes(u64::from(*value) * u64::from(*value), &barrett)
+                as Self;
+        }
+    }
+}
 
 impl PrimitiveUint for u64 {
     fn reduce_from_wide<const M: u64>(value: Self::Wide) -> Self {
-        // This is the only integer size for which this gives a speed-up.
         let reciprocal = Modulus::<Self, M>::RECIPROCAL;
         rem_wide_with_reciprocal(value, &reciprocal)
     }
+
+    #[cfg(feature = "montgomery64")]
+    impl_montgomery!();
 }
 
 impl WideUint for u32 {}
@@ -0,0 +1,458 @@
+//! A curve with the same group as [`TinyCurve16`], where every element knows its discrete log.
+//!
+//! Group elements are stored as scalars `k` such that the element is `k * G`.
+//! The group operations are performed on the scalars,
+//! and the actual curve points are only calculated when the coordinates or the encoding are requested.
+//! Since the results are the same as for [`TinyCurve16`], protocols can run unchanged,
+//! but all the intermediate values stay inspectable.
+
+use core::{
+    fmt,
+    iter::Sum,
+    ops::{Add, AddAssign, Mul, MulAssign, Neg, Sub, SubAssign},
+};
+
+use primeorder::elliptic_curve::{
+    generic_array::typenum::Unsigned,
+    group::{self, Group},
+    ops::{LinearCombination, MulByGenerator},
+    point::{AffineCoordinates, DecompressPoint, PointCompression},
+    rand_core::RngCore,
+    sec1::{EncodedPoint, FromEncodedPoint, ToEncodedPoint},
+    subtle::{Choice, ConditionallySelectable, ConstantTimeEq, CtOption},
+    zeroize::DefaultIsZeroes,
+    AffinePoint, Curve, CurveArithmetic, Field, FieldBytes, FieldBytesEncoding, PrimeCurve,
+    ProjectivePoint,
+};
+
+#[cfg(feature = "ecdsa")]
+use ::ecdsa::hazmat::{DigestPrimitive, VerifyPrimitive};
+#[cfg(feature = "ecdsa")]
+use sha2::Sha256;
+
+use crate::{
+    curve16::ORDER,
+    prime_field::{ReprSizeTypenum, ReprUint},
+    TinyCurve16,
+};
+
+#[cfg(feature = "ecdsa")]
+use crate::hash::TinyHash;
+
+type Scalar = <TinyCurve16 as CurveArithmetic>::Scalar;
+
+/// An elliptic curve with the same group as [`TinyCurve16`]
+/// where every group element stores its discrete log with respect to the generator.
+///
+/// See [`TransparentPoint`] for details.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord)]
+pub struct TransparentCurve;
+
+impl Curve for TransparentCurve {
+    type FieldBytesSize = ReprSizeTypenum;
+    type Uint = ReprUint;
+    const ORDER: Self::Uint = Self::Uint::from_u64(ORDER);
+}
+
+impl FieldBytesEncoding<TransparentCurve> for <TransparentCurve as Curve>::Uint {}
+
+impl CurveArithmetic for TransparentCurve {
+    type Scalar = Scalar;
+    type AffinePoint = TransparentPoint;
+    type ProjectivePoint = TransparentPoint;
+}
+
+impl PrimeCurve for TransparentCurve {}
+
+impl PointCompression for TransparentCurve {
+    const COMPRESS_POINTS: bool = true;
+}
+
+#[cfg(feature = "ecdsa")]
+impl VerifyPrimitive<TransparentCurve> for TransparentPoint {}
+
+#[cfg(feature = "ecdsa")]
+impl DigestPrimitive for TransparentCurve {
+    type Digest = TinyHash<Sha256, 2>;
+}
+
+/// A point of [`TransparentCurve`] (serves as both the affine and the projective representation).
+///
+/// Internally it is stored as its discrete log `k` with respect to the generator `G`,
+/// which can be retrieved with [`discrete_log`](`Self::discrete_log`),
+/// and is shown by the [`Debug`] implementation as `G·k`.
+///
+/// The coordinates and the SEC1 encoding are those of the corresponding point of [`TinyCurve16`].
+/// Decoding a point requires finding its discrete log,
+/// which takes a few hundred group operations.
+#[derive(Default, Clone, Copy, PartialEq, Eq)]
+pub struct TransparentPoint(Scalar);
+
+impl TransparentPoint {
+    /// The identity element.
+    pub const IDENTITY: Self = Self(Scalar::ZERO);
+
+    /// The generator.
+    pub const GENERATOR: Self = Self(Scalar::ONE);
+
+    /// Creates the point `k * G`.
+    pub const fn from_discrete_log(k: Scalar) -> Self {
+        Self(k)
+    }
+
+    /// Returns `k` such that this point is `k * G`.
+    pub const fn discrete_log(&self) -> Scalar {
+        self.0
+    }
+
+    /// Returns the corresponding point of [`TinyCurve16`].
+    pub fn to_tiny_curve(&self) -> AffinePoint<TinyCurve16> {
+        ProjectivePoint::<TinyCurve16>::mul_by_generator(&self.0).to_affine()
+    }
+
+    /// Finds the discrete log of a point of [`TinyCurve16`].
+    pub fn from_tiny_curve(point: &AffinePoint<TinyCurve16>) -> Self {
+        Self(discrete_log(point))
+    }
+}
+
+/// The number of baby steps in [`discrete_log`]: `ceil(sqrt(ORDER))`.
+const BABY_STEPS: usize = 256;
+
+/// Returns the last 8 bytes of the x-coordinate of a point (which is enough to contain the whole value).
+fn x_as_u64(point: &AffinePoint<TinyCurve16>) -> u64 {
+    let x = point.x();
+    u64::from_be_bytes(
+        x[ReprSizeTypenum::USIZE - 8..]
+            .try_into()
+            .expect("slice has the correct length"),
+    )
+}
+
+/// Finds the discrete log of a point with the baby-step giant-step algorithm.
+fn discrete_log(point: &AffinePoint<TinyCurve16>) -> Scalar {
+    // Baby steps: the x-coordinates of `j * G` for `j` in `[1, BABY_STEPS]`.
+    // Since `j * G` and `-j * G` have the same x-coordinate,
+    // this covers the range `[-BABY_STEPS, BABY_STEPS]`.
+    let generator = ProjectivePoint::<TinyCurve16>::generator();
+    let mut baby_steps = [(0u64, 0u16); BABY_STEPS];
+    let mut baby_step = ProjectivePoint::<TinyCurve16>::identity();
+    for (j, entry) in baby_steps.iter_mut().enumerate() {
+        baby_step += generator;
+        *entry = (
+            x_as_u64(&baby_step.to_affine()),
+            (j + 1).try_into().expect("`j` fits into `u16`"),
+        );
+    }
+    baby_steps.sort_unstable();
+
+    // Giant steps: `point - i * (2 * BABY_STEPS + 1) * G`, until it lands in the baby step range.
+    let giant_step_size = Scalar::from(2 * BABY_STEPS as u64 + 1);
+    let giant_step = -(generator * giant_step_size);
+    let mut giant_step_scalar = Scalar::ZERO;
+    let mut current = ProjectivePoint::<TinyCurve16>::from(*point);
+    loop {
+        if bool::from(current.is_identity()) {
+            return giant_step_scalar;
+        }
+
+        let current_affine = current.to_affine();
+        if let Ok(idx) =
+            baby_steps.binary_search_by_key(&x_as_u64(&current_affine), |entry| entry.0)
+        {
+            let j = Scalar::from(u64::from(baby_steps[idx].1));
+            let candidate = generator * j;
+            return if candidate.to_affine() == current_affine {
+                giant_step_scalar + j
+            } else {
+                giant_step_scalar - j
+            };
+        }
+
+        current += giant_step;
+        giant_step_scalar += giant_step_size;
+    }
+}
+
+impl fmt::Debug for TransparentPoint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let k: ReprUint = self.0.into();
+        write!(f, "G·{}", k.as_words()[0])
+    }
+}
+
+impl AffineCoordinates for TransparentPoint {
+    type FieldRepr = FieldBytes<TransparentCurve>;
+
+    fn x(&self) -> Self::FieldRepr {
+        self.to_tiny_curve().x()
+    }
+
+    fn y_is_odd(&self) -> Choice {
+        self.to_tiny_curve().y_is_odd()
+    }
+}
+
+impl ConditionallySelectable for TransparentPoint {
+    fn conditional_select(lhs: &Self, rhs: &Self, choice: Choice) -> Self {
+        Self(Scalar::conditional_select(&lhs.0, &rhs.0, choice))
+    }
+}
+
+impl ConstantTimeEq for TransparentPoint {
+    fn ct_eq(&self, rhs: &Self) -> Choice {
+        self.0.ct_eq(&rhs.0)
+    }
+}
+
+impl DefaultIsZeroes for TransparentPoint {}
+
+impl DecompressPoint<TransparentCurve> for TransparentPoint {
+    fn decompress(x: &FieldBytes<TransparentCurve>, y_is_odd: Choice) -> CtOption<Self> {
+        AffinePoint::<TinyCurve16>::decompress(x, y_is_odd)
+            .map(|point| Self::from_tiny_curve(&point))
+    }
+}
+
+impl FromEncodedPoint<TransparentCurve> for TransparentPoint {
+    fn from_encoded_point(point: &EncodedPoint<TransparentCurve>) -> CtOption<Self> {
+        let point = EncodedPoint::<TinyCurve16>::from_bytes(point.as_bytes())
+            .expect("the encodings of `TinyCurve16` and `TransparentCurve` are the same");
+        AffinePoint::<TinyCurve16>::from_encoded_point(&point)
+            .map(|point| Self::from_tiny_curve(&point))
+    }
+}
+
+impl ToEncodedPoint<TransparentCurve> for TransparentPoint {
+    fn to_encoded_point(&self, compress: bool) -> EncodedPoint<TransparentCurve> {
+        EncodedPoint::<TransparentCurve>::from_bytes(
+            self.to_tiny_curve().to_encoded_point(compress).as_bytes(),
+        )
+        .expect("the encodings of `TinyCurve16` and `TransparentCurve` are the same")
+    }
+}
+
+impl<'a> AddAssign<&'a Self> for TransparentPoint {
+    fn add_assign(&mut self, rhs: &'a Self) {
+        self.0 += rhs.0
+    }
+}
+
+impl AddAssign<Self> for TransparentPoint {
+    fn add_assign(&mut self, rhs: Self) {
+        *self += &rhs
+    }
+}
+
+impl Add<Self> for TransparentPoint {
+    type Output = Self;
+    fn add(mut self, rhs: Self) -> Self::Output {
+        self += rhs;
+        self
+    }
+}
+
+impl<'a> Add<&'a Self> for TransparentPoint {
+    type Output = Self;
+    fn add(mut self, rhs: &'a Self) -> Self::Output {
+        self += rhs;
+        self
+    }
+}
+
+impl<'a> SubAssign<&'a Self> for TransparentPoint {
+    fn sub_assign(&mut self, rhs: &'a Self) {
+        self.0 -= rhs.0
+    }
+}
+
+impl SubAssign<Self> for TransparentPoint {
+    fn sub_assign(&mut self, rhs: Self) {
+        *self -= &rhs
+    }
+}
+
+impl Sub<Self> for TransparentPoint {
+    type Output = Self;
+    fn sub(mut self, rhs: Self) -> Self::Output {
+        self -= rhs;
+        self
+    }
+}
+
+impl<'a> Sub<&'a Self> for TransparentPoint {
+    type Output = Self;
+    fn sub(mut self, rhs: &'a Self) -> Self::Output {
+        self -= rhs;
+        self
+    }
+}
+
+impl<'a> MulAssign<&'a Scalar> for TransparentPoint {
+    fn mul_assign(&mut self, rhs: &'a Scalar) {
+        self.0 *= rhs
+    }
+}
+
+impl MulAssign<Scalar> for TransparentPoint {
+    fn mul_assign(&mut self, rhs: Scalar) {
+        *self *= &rhs
+    }
+}
+
+impl Mul<Scalar> for TransparentPoint {
+    type Output = Self;
+    fn mul(mut self, rhs: Scalar) -> Self::Output {
+        self *= rhs;
+        self
+    }
+}
+
+impl<'a> Mul<&'a Scalar> for TransparentPoint {
+    type Output = Self;
+    fn mul(mut self, rhs: &'a Scalar) -> Self::Output {
+        self *= rhs;
+        self
+    }
+}
+
+impl Neg for TransparentPoint {
+    type Output = Self;
+    fn neg(self) -> Self::Output {
+        Self(-self.0)
+    }
+}
+
+impl Sum for TransparentPoint {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::IDENTITY, Add::add)
+    }
+}
+
+impl<'a> Sum<&'a Self> for TransparentPoint {
+    fn sum<I: Iterator<Item = &'a Self>>(iter: I) -> Self {
+        iter.fold(Self::IDENTITY, Add::add)
+    }
+}
+
+impl Group for TransparentPoint {
+    type Scalar = Scalar;
+
+    fn random(rng: impl RngCore) -> Self {
+        Self(Scalar::random(rng))
+    }
+
+    fn identity() -> Self {
+        Self::IDENTITY
+    }
+
+    fn generator() -> Self {
+        Self::GENERATOR
+    }
+
+    fn is_identity(&self) -> Choice {
+        self.0.is_zero()
+    }
+
+    fn double(&self) -> Self {
+        Self(self.0.double())
+    }
+}
+
+impl group::Curve for TransparentPoint {
+    type AffineRepr = Self;
+
+    fn to_affine(&self) -> Self::AffineRepr {
+        *self
+    }
+}
+
+impl LinearCombination for TransparentPoint {}
+
+impl MulByGenerator for TransparentPoint {}
+
+#[cfg(test)]
+mod tests {
+    use primeorder::elliptic_curve::{
+        group::Group,
+        ops::MulByGenerator,
+        sec1::{FromEncodedPoint, ToEncodedPoint},
+        CurveArithmetic, Field, ProjectivePoint,
+    };
+    use rand_core::OsRng;
+
+    use super::TransparentPoint;
+    use crate::TinyCurve16;
+
+    type Scalar = <TinyCurve16 as CurveArithmetic>::Scalar;
+
+    #[test]
+    fn debug() {
+        let p = TransparentPoint::generator() * Scalar::from(1234u64);
+        assert_eq!(format!("{:?}", p), "G·1234");
+        assert_eq!(format!("{:?}", TransparentPoint::identity()), "G·0");
+        assert_eq!(format!("{:?}", -TransparentPoint::generator()), "G·65170");
+    }
+
+    #[test]
+    fn discrete_log() {
+        for _ in 0..16 {
+            let k = Scalar::random(&mut OsRng);
+            let p = TransparentPoint::mul_by_generator(&k);
+            assert_eq!(p.discrete_log(), k);
+
+            let p_tc = ProjectivePoint::<TinyCurve16>::mul_by_generator(&k).to_affine();
+            assert_eq!(p.to_tiny_curve(), p_tc);
+            assert_eq!(TransparentPoint::from_tiny_curve(&p_tc), p);
+        }
+
+        // Edge cases
+        for k in [0u64, 1, 2, 255, 256, 257, 512, 513, 514, 65169, 65170] {
+            let p = TransparentPoint::from_discrete_log(Scalar::from(k));
+            assert_eq!(TransparentPoint::from_tiny_curve(&p.to_tiny_curve()), p);
+        }
+    }
+
+    #[test]
+    fn encoding() {
+        let p = TransparentPoint::random(&mut OsRng);
+        for compress in [false, true] {
+            let encoded = p.to_encoded_point(compress);
+            assert_eq!(
+                encoded.as_bytes(),
+                p.to_tiny_curve().to_encoded_point(compress).as_bytes()
+            );
+            assert_eq!(TransparentPoint::from_encoded_point(&encoded).unwrap(), p);
+        }
+    }
+}
+
+#[cfg(all(test, feature = "ecdsa"))]
+mod tests_ecdsa {
+    use ecdsa::{signature::hazmat::PrehashVerifier, SigningKey, VerifyingKey};
+    use primeorder::elliptic_curve::PrimeField;
+    use rand_core::OsRng;
+
+    use super::TransparentCurve;
+    use crate::TinyCurve16;
+
+    #[test]
+    fn signatures_are_interchangeable() {
+        let prehash = b"123456781234567812345678";
+        let sk = SigningKey::<TransparentCurve>::random(&mut OsRng);
+
+        let (signature, recovery_id) = sk.sign_prehash_recoverable(prehash).unwrap();
+        let vk = VerifyingKey::recover_from_prehash(prehash, &signature, recovery_id).unwrap();
+        assert_eq!(sk.verifying_key(), &vk);
+
+        // The same key and signature work with `TinyCurve16`
+        let sk_tc =
+            SigningKey::<TinyCurve16>::from_bytes(&sk.as_nonzero_scalar().to_repr()).unwrap();
+        let signature_tc =
+            ecdsa::Signature::<TinyCurve16>::from_slice(&signature.to_bytes()).unwrap();
+        sk_tc
+            .verifying_key()
+            .verify_prehash(prehash, &signature_tc)
+            .unwrap();
+    }
+}
@@ -0,0 +1,403 @@
+//! Witnesses for low-probability events.
+//!
+//! With the small orders of the curves in this crate, events with probability around `1/n`
+//! can be reached by a search.
+//! The `find_*` functions perform such searches, and [`KnownWitnesses`] caches their results
+//! for the curves in this crate (or `None` where the search is not feasible).
+//!
+//! Note that there are no witnesses for a product of nonzero scalars being zero:
+//! since the orders are prime, this never happens.
+
+use core::ops::Range;
+
+#[cfg(feature = "ecdsa")]
+use ecdsa::{
+    hazmat::{bits2field, DigestPrimitive, SignPrimitive},
+    SignatureSize, SigningKey,
+};
+#[cfg(feature = "ecdsa")]
+use primeorder::elliptic_curve::{
+    generic_array::ArrayLength,
+    ops::{Invert, Reduce},
+    scalar::IsHigh,
+    subtle::CtOption,
+    Field, PrimeCurve, Scalar,
+};
+#[cfg(feature = "ecdsa")]
+use sha2::Digest;
+
+#[cfg(feature = "bip32")]
+use bip32::{ChildNumber, PrivateKey as _};
+#[cfg(feature = "bip32")]
+use primeorder::elliptic_curve::{
+    sec1::{FromEncodedPoint, ModulusSize, ToEncodedPoint},
+    Curve, SecretKey,
+};
+
+use primeorder::elliptic_curve::{CurveArithmetic, NonZeroScalar};
+
+use crate::{TinyCurve16, TinyCurve32, TinyCurve64};
+
+#[cfg(feature = "bip32")]
+use crate::PrivateKeyBip32;
+
+/// A rare event in ECDSA signing (with [`SigningKey::sign_recoverable`]).
+#[cfg(feature = "ecdsa")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EcdsaEvent {
+    /// The x-coordinate of `R` is greater than or equal to the order,
+    /// so the recovery id is 2 or 3.
+    XOverflow,
+    /// `s` is greater than `n / 2`.
+    HighS,
+    /// The message hash reduces to zero modulo the order.
+    ZeroHash,
+}
+
+/// A secret key and a message for which an [`EcdsaEvent`] occurs.
+#[cfg(feature = "ecdsa")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EcdsaWitness {
+    /// The secret key.
+    pub secret_key: u64,
+    /// The message (a big-endian counter, as produced by [`find_ecdsa_witness`]).
+    pub message: [u8; 8],
+}
+
+#[cfg(feature = "ecdsa")]
+impl EcdsaWitness {
+    /// Returns the signing key for this witness.
+    ///
+    /// Panics if the secret key is zero modulo the curve order.
+    pub fn signing_key<C>(&self) -> SigningKey<C>
+    where
+        C: PrimeCurve + CurveArithmetic + DigestPrimitive,
+        Scalar<C>: Invert<Output = CtOption<Scalar<C>>> + SignPrimitive<C> + From<u64>,
+        SignatureSize<C>: ArrayLength<u8>,
+    {
+        let scalar: Option<NonZeroScalar<C>> =
+            NonZeroScalar::new(Scalar::<C>::from(self.secret_key)).into();
+        SigningKey::from(scalar.expect("the secret key is nonzero"))
+    }
+
+    /// Returns `true` if `event` occurs when signing the message with the secret key.
+    ///
+    /// Signing fails for about `3/n` of the messages (if `r = 0` or `s = 0`);
+    /// the events [`EcdsaEvent::XOverflow`] and [`EcdsaEvent::HighS`] do not occur for them.
+    pub fn has_event<C>(&self, event: EcdsaEvent) -> bool
+    where
+        C: PrimeCurve + CurveArithmetic + DigestPrimitive,
+        Scalar<C>: Invert<Output = CtOption<Scalar<C>>> + SignPrimitive<C> + From<u64>,
+        SignatureSize<C>: ArrayLength<u8>,
+    {
+        match event {
+            EcdsaEvent::ZeroHash => {
+                let prehash = C::Digest::digest(self.message);
+                let z = bits2field::<C>(&prehash).expect("the digest is large enough");
+                <Scalar<C> as Reduce<C::Uint>>::reduce_bytes(&z)
+                    .is_zero()
+                    .into()
+            }
+            EcdsaEvent::XOverflow | EcdsaEvent::HighS => {
+                let Ok((signature, recovery_id)) =
+                    self.signing_key::<C>().sign_recoverable(&self.message)
+                else {
+                    return false;
+                };
+                if event == EcdsaEvent::XOverflow {
+                    recovery_id.is_x_reduced()
+                } else {
+                    signature.s().is_high().into()
+                }
+            }
+        }
+    }
+}
+
+/// Searches for a message (a big-endian counter from `counters`)
+/// for which `event` occurs when signing with `secret_key`.
+#[cfg(feature = "ecdsa")]
+pub fn find_ecdsa_witness<C>(
+    event: EcdsaEvent,
+    secret_key: u64,
+    counters: Range<u64>,
+) -> Option<EcdsaWitness>
+where
+    C: PrimeCurve + CurveArithmetic + DigestPrimitive,
+    Scalar<C>: Invert<Output = CtOption<Scalar<C>>> + SignPrimitive<C> + From<u64>,
+    SignatureSize<C>: ArrayLength<u8>,
+{
+    counters
+        .map(|counter| EcdsaWitness {
+            secret_key,
+            message: counter.to_be_bytes(),
+        })
+        .find(|witness| witness.has_event::<C>(event))
+}
+
+/// A BIP32 parent key and a child number for which the child derivation fails.
+#[cfg(feature = "bip32")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Bip32Witness {
+    /// The parent private key.
+    pub private_key: u64,
+    /// The parent chain code.
+    pub chain_code: [u8; 32],
+    /// The child number.
+    pub child_number: u32,
+}
+
+#[cfg(feature = "bip32")]
+impl Bip32Witness {
+    /// Returns `true` if deriving the child private key fails
+    /// (the tweak is out of range or the child key is zero).
+    ///
+    /// Panics if the private key is zero modulo the curve order.
+    pub fn is_invalid_child<C>(&self) -> bool
+    where
+        C: Curve + CurveArithmetic,
+        C::AffinePoint: ToEncodedPoint<C> + FromEncodedPoint<C>,
+        C::FieldBytesSize: ModulusSize,
+        C::Scalar: From<u64>,
+    {
+        let scalar: Option<NonZeroScalar<C>> =
+            NonZeroScalar::new(C::Scalar::from(self.private_key)).into();
+        let parent = PrivateKeyBip32::<C>::from(SecretKey::from(
+            scalar.expect("the private key is nonzero"),
+        ));
+        parent
+            .derive_tweak(&self.chain_code, ChildNumber(self.child_number))
+            .and_then(|(tweak, _chain_code)| parent.derive_child(tweak))
+            .is_err()
+    }
+}
+
+/// Searches for a child number in `child_numbers` for which the derivation fails.
+#[cfg(feature = "bip32")]
+pub fn find_bip32_witness<C>(
+    private_key: u64,
+    chain_code: [u8; 32],
+    child_numbers: Range<u32>,
+) -> Option<Bip32Witness>
+where
+    C: Curve + CurveArithmetic,
+    C::AffinePoint: ToEncodedPoint<C> + FromEncodedPoint<C>,
+    C::FieldBytesSize: ModulusSize,
+    C::Scalar: From<u64>,
+{
+    child_numbers
+        .map(|child_number| Bip32Witness {
+            private_key,
+            chain_code,
+            child_number,
+        })
+        .find(|witness| witness.is_invalid_child::<C>())
+}
+
+/// The secret key used for the cached witnesses.
+pub const WITNESS_SECRET_KEY: u64 = 0x1234;
+
+/// The chain code used for the cached BIP32 witnesses.
+#[cfg(feature = "bip32")]
+pub const WITNESS_CHAIN_CODE: [u8; 32] = [1; 32];
+
+/// Cached witnesses, found with [`WITNESS_SECRET_KEY`] (and [`WITNESS_CHAIN_CODE`])
+/// as the first message counter or child number starting from 0.
+///
+/// `None` means the search is not feasible for the curve.
+pub trait KnownWitnesses: CurveArithmetic {
+    /// A witness for [`EcdsaEvent::XOverflow`].
+    #[cfg(feature = "ecdsa")]
+    const ECDSA_X_OVERFLOW: Option<EcdsaWitness>;
+
+    /// A witness for [`EcdsaEvent::HighS`].
+    #[cfg(feature = "ecdsa")]
+    const ECDSA_HIGH_S: Option<EcdsaWitness>;
+
+    /// A witness for [`EcdsaEvent::ZeroHash`].
+    #[cfg(feature = "ecdsa")]
+    const ECDSA_ZERO_HASH: Option<EcdsaWitness>;
+
+    /// A witness for a failing BIP32 child derivation.
+    #[cfg(feature = "bip32")]
+    const BIP32_INVALID_CHILD: Option<Bip32Witness>;
+}
+
+#[cfg(feature = "ecdsa")]
+const fn ecdsa_witness(counter: u64) -> Option<EcdsaWitness> {
+    Some(EcdsaWitness {
+        secret_key: WITNESS_SECRET_KEY,
+        message: counter.to_be_bytes(),
+    })
+}
+
+#[cfg(feature = "bip32")]
+const fn bip32_witness(child_number: u32) -> Option<Bip32Witness> {
+    Some(Bip32Witness {
+        private_key: WITNESS_SECRET_KEY,
+        chain_code: WITNESS_CHAIN_CODE,
+        child_number,
+    })
+}
+
+impl KnownWitnesses for TinyCurve16 {
+    #[cfg(feature = "ecdsa")]
+    const ECDSA_X_OVERFLOW: Option<EcdsaWitness> = ecdsa_witness(59);
+    #[cfg(feature = "ecdsa")]
+    const ECDSA_HIGH_S: Option<EcdsaWitness> = ecdsa_witness(0);
+    #[cfg(feature = "ecdsa")]
+    const ECDSA_ZERO_HASH: Option<EcdsaWitness> = ecdsa_witness(30556);
+    #[cfg(feature = "bip32")]
+    const BIP32_INVALID_CHILD: Option<Bip32Witness> = bip32_witness(91);
+}
+
+impl KnownWitnesses for TinyCurve32 {
+    #[cfg(feature = "ecdsa")]
+    const ECDSA_X_OVERFLOW: Option<EcdsaWitness> = ecdsa_witness(32607);
+    #[cfg(feature = "ecdsa")]
+    const ECDSA_HIGH_S: Option<EcdsaWitness> = ecdsa_witness(1);
+    #[cfg(feature = "ecdsa")]
+    const ECDSA_ZERO_HASH: Option<EcdsaWitness> = ecdsa_witness(12890039);
+    #[cfg(feature = "bip32")]
+    const BIP32_INVALID_CHILD: Option<Bip32Witness> = bip32_witness(32826);
+}
+
+impl KnownWitnesses for TinyCurve64 {
+    // `R.x >= n` happens with probability about `2^(-32)`
+    #[cfg(feature = "ecdsa")]
+    const ECDSA_X_OVERFLOW: Option<EcdsaWitness> = None;
+    #[cfg(feature = "ecdsa")]
+    const ECDSA_HIGH_S: Option<EcdsaWitness> = ecdsa_witness(0);
+    #[cfg(feature = "ecdsa")]
+    const ECDSA_ZERO_HASH: Option<EcdsaWitness> = None;
+    // The tweak is out of range with probability about `2^(-32)`
+    #[cfg(feature = "bip32")]
+    const BIP32_INVALID_CHILD: Option<Bip32Witness> = None;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(feature = "ecdsa")]
+    fn check_ecdsa<C>()
+    where
+        C: KnownWitnesses + PrimeCurve + DigestPrimitive,
+        Scalar<C>: Invert<Output = CtOption<Scalar<C>>> + SignPrimitive<C> + From<u64>,
+        SignatureSize<C>: ArrayLength<u8>,
+    {
+        for (witness, event) in [
+            (C::ECDSA_X_OVERFLOW, EcdsaEvent::XOverflow),
+            (C::ECDSA_HIGH_S, EcdsaEvent::HighS),
+            (C::ECDSA_ZERO_HASH, EcdsaEvent::ZeroHash),
+        ] {
+            if let Some(witness) = witness {
+                assert_eq!(witness.secret_key, WITNESS_SECRET_KEY);
+                assert!(witness.has_event::<C>(event));
+
+                // The witness is the first one
+                let counter = u64::from_be_bytes(witness.message);
+                assert_eq!(
+                    find_ecdsa_witness::<C>(event, WITNESS_SECRET_KEY, 0..counter),
+                    None
+                );
+            }
+        }
+    }
+
+    #[cfg(feature = "ecdsa")]
+    #[test]
+    fn ecdsa_witnesses() {
+        check_ecdsa::<TinyCurve16>();
+        check_ecdsa::<TinyCurve64>();
+
+        // Not checking that these are the first witnesses, the search takes too long.
+        for (witness, event) in [
+            (TinyCurve32::ECDSA_X_OVERFLOW, EcdsaEvent::XOverflow),
+            (TinyCurve32::ECDSA_HIGH_S, EcdsaEvent::HighS),
+            (TinyCurve32::ECDSA_ZERO_HASH, EcdsaEvent::ZeroHash),
+        ] {
+            if let Some(witness) = witness {
+                assert!(witness.has_event::<TinyCurve32>(event));
+            }
+        }
+    }
+
+    #[cfg(feature = "ecdsa")]
+    #[test]
+    fn ecdsa_signing_fails() {
+        // Signing with the witness key fails for this message
+        const FAILING_COUNTER: u64 = 22635;
+        let witness = EcdsaWitness {
+            secret_key: WITNESS_SECRET_KEY,
+            message: FAILING_COUNTER.to_be_bytes(),
+        };
+        assert!(witness
+            .signing_key::<TinyCurve16>()
+            .sign_recoverable(&witness.message)
+            .is_err());
+
+        for event in [EcdsaEvent::XOverflow, EcdsaEvent::HighS] {
+            assert!(!witness.has_event::<TinyCurve16>(event));
+            assert_eq!(
+                find_ecdsa_witness::<TinyCurve16>(
+                    event,
+                    WITNESS_SECRET_KEY,
+                    FAILING_COUNTER..FAILING_COUNTER + 1
+                ),
+                None
+            );
+            // The search continues past the failing message
+            let found = find_ecdsa_witness::<TinyCurve16>(
+                event,
+                WITNESS_SECRET_KEY,
+                FAILING_COUNTER..FAILING_COUNTER + 4096,
+            )
+            .unwrap();
+            assert!(u64::from_be_bytes(found.message) > FAILING_COUNTER);
+        }
+    }
+
+    #[cfg(feature = "ecdsa")]
+    #[test]
+    fn ecdsa_signatures() {
+        use ecdsa::{signature::Verifier, VerifyingKey};
+
+        // Signatures with the rare events still verify and recover the key
+        for (witness, event) in [
+            (TinyCurve16::ECDSA_X_OVERFLOW, EcdsaEvent::XOverflow),
+            (TinyCurve16::ECDSA_ZERO_HASH, EcdsaEvent::ZeroHash),
+        ] {
+            let witness = witness.unwrap();
+            let signing_key = witness.signing_key::<TinyCurve16>();
+            let verifying_key = VerifyingKey::from(&signing_key);
+            let (signature, recovery_id) = signing_key.sign_recoverable(&witness.message).unwrap();
+            if event == EcdsaEvent::XOverflow {
+                assert!(recovery_id.to_byte() >= 2);
+            }
+            assert!(verifying_key.verify(&witness.message, &signature).is_ok());
+            assert_eq!(
+                VerifyingKey::recover_from_msg(&witness.message, &signature, recovery_id).unwrap(),
+                verifying_key
+            );
+        }
+    }
+
+    #[cfg(feature = "bip32")]
+    #[test]
+    fn bip32_witnesses() {
+        let witness = TinyCurve16::BIP32_INVALID_CHILD.unwrap();
+        assert!(witness.is_invalid_child::<TinyCurve16>());
+        assert_eq!(
+            find_bip32_witness::<TinyCurve16>(
+                WITNESS_SECRET_KEY,
+                WITNESS_CHAIN_CODE,
+                0..witness.child_number + 1
+            ),
+            Some(witness)
+        );
+
+        let witness = TinyCurve32::BIP32_INVALID_CHILD.unwrap();
+        assert!(witness.is_invalid_child::<TinyCurve32>());
+    }
+}
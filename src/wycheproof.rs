@@ -0,0 +1,762 @@
+//! Test vector generation in the [Wycheproof](https://github.com/C2SP/wycheproof) format.
+//!
+//! [`ecdsa_verify_vectors`] produces an `EcdsaVerify` file (`ecdsa_verify_schema_v1.json`),
+//! and [`ecdh_vectors`] an `EcdhEcpointTest` file (`ecdh_ecpoint_test_schema_v1.json`).
+//! The vectors are deterministic and focus on the edge cases that are easy to reach
+//! with the small orders: `r` or `s` equal to 0 or `n`, `R.x >= n`, high `s`,
+//! intermediate points at infinity, and invalid point and signature encodings.
+//!
+//! The hash is SHA-256 truncated to the bit size of the order, which is what
+//! [`TinyHash`](crate::TinyHash) computes.
+//! The points use the encodings of this crate (24-byte coordinates),
+//! and the ECDH shared secrets are the 24-byte x-coordinates of the shared points.
+//!
+//! The binary `tiny-curve-wycheproof` writes the files for all the curves.
+
+use alloc::{format, string::String, vec, vec::Vec};
+
+use primeorder::{
+    elliptic_curve::{
+        pkcs8::{AssociatedOid, EncodePublicKey, LineEnding},
+        sec1::{EncodedPoint, FromEncodedPoint, ModulusSize, ToEncodedPoint},
+        FieldBytes, PublicKey,
+    },
+    AffinePoint, PrimeCurveParams,
+};
+use serde_json::{json, Value};
+use sha2::{Digest, Sha256};
+
+use crate::{curve16, curve32, curve64, Point, TinyCurve16, TinyCurve32, TinyCurve64};
+
+/// A curve for which Wycheproof test vectors can be generated.
+pub trait WycheproofCurve: PrimeCurveParams + AssociatedOid {
+    /// The curve name used in the test vectors.
+    const NAME: &'static str;
+
+    /// The order of the curve.
+    const ORDER_U64: u64;
+
+    /// The field modulus of the curve.
+    const FIELD_MODULUS_U64: u64;
+}
+
+macro_rules! impl_wycheproof_curve {
+    ($curve:ident, $module:ident) => {
+        impl WycheproofCurve for $curve {
+            const NAME: &'static str = stringify!($curve);
+            const ORDER_U64: u64 = $module::ORDER;
+            const FIELD_MODULUS_U64: u64 = $module::FIELD_MODULUS;
+        }
+    };
+}
+
+impl_wycheproof_curve!(TinyCurve16, curve16);
+impl_wycheproof_curve!(TinyCurve32, curve32);
+impl_wycheproof_curve!(TinyCurve64, curve64);
+
+/// Returns the test vector files for all the curves in this crate, along with their file names.
+pub fn test_vector_files() -> Vec<(String, Value)> {
+    let mut files = Vec::new();
+    files.extend(curve_files::<TinyCurve16>());
+    files.extend(curve_files::<TinyCurve32>());
+    files.extend(curve_files::<TinyCurve64>());
+    files
+}
+
+fn curve_files<C>() -> [(String, Value); 2]
+where
+    C: WycheproofCurve,
+    AffinePoint<C>: FromEncodedPoint<C> + ToEncodedPoint<C>,
+    C::FieldBytesSize: ModulusSize,
+{
+    let name = C::NAME.to_lowercase();
+    [
+        (
+            format!("ecdsa_{name}_sha256_test.json"),
+            ecdsa_verify_vectors::<C>(),
+        ),
+        (
+            format!("ecdh_{name}_ecpoint_test.json"),
+            ecdh_vectors::<C>(),
+        ),
+    ]
+}
+
+/// Returns the ECDSA verification test vectors (an `EcdsaVerify` file) for the curve.
+pub fn ecdsa_verify_vectors<C>() -> Value
+where
+    C: WycheproofCurve,
+    AffinePoint<C>: FromEncodedPoint<C> + ToEncodedPoint<C>,
+    C::FieldBytesSize: ModulusSize,
+{
+    let n = C::ORDER_U64;
+    let mut tests = TestList::default();
+
+    // A key with a known secret.
+    let d = scalar_from_label::<C>("ecdsa key");
+    let public = Point::<C>::GENERATOR.mul_u64(d);
+    let mut group = Vec::new();
+
+    for msg in [&b""[..], b"Msg", b"123400"] {
+        let (r, s) = sign::<C>(d, msg, scalar_from_label::<C>(&format!("k {msg:?}")));
+        group.push(tests.push(
+            "valid signature",
+            &["ValidSignature"],
+            msg,
+            &der_signature(&[r.into(), s.into()]),
+            "valid",
+        ));
+    }
+
+    let msg = b"123400";
+    let (r, s) = sign::<C>(d, msg, scalar_from_label::<C>("k"));
+    let high_s = if s > n / 2 { s } else { n - s };
+    let (r, s) = (u128::from(r), u128::from(s));
+    let n_wide = u128::from(n);
+    group.push(tests.push(
+        "high s",
+        &["SignatureMalleabilityBitcoin"],
+        msg,
+        &der_signature(&[r, high_s.into()]),
+        "valid",
+    ));
+
+    for (comment, flags, r, s) in [
+        ("r = 0", &["InvalidSignature"][..], 0, s),
+        ("s = 0", &["InvalidSignature"], r, 0),
+        ("r = s = 0", &["InvalidSignature"], 0, 0),
+        ("r = n", &["RangeCheck"], n_wide, s),
+        ("s = n", &["RangeCheck"], r, n_wide),
+        ("r + n", &["RangeCheck"], r + n_wide, s),
+        ("s + n", &["RangeCheck"], r, s + n_wide),
+        ("r = n - 1", &["ModifiedInteger"], n_wide - 1, s),
+        ("s = n - 1", &["ModifiedInteger"], r, n_wide - 1),
+        ("r = 1, s = 1", &["SmallRandS"], 1, 1),
+    ] {
+        group.push(tests.push(comment, flags, msg, &der_signature(&[r, s]), "invalid"));
+    }
+
+    // `r` chosen so that `u1 * G + u2 * Q` is the point at infinity:
+    // `(z + r * d) / s = 0`, so `r = -z / d`.
+    let z = hash_to_scalar::<C>(msg);
+    let r_infinity = mul_mod(n - z, invert_mod(d, n), n);
+    group.push(tests.push(
+        "u1 * G + u2 * Q is the point at infinity",
+        &["ArithmeticError"],
+        msg,
+        &der_signature(&[r_infinity.into(), s]),
+        "invalid",
+    ));
+
+    // Invalid DER encodings of a valid signature
+    let valid = der_signature(&[r, s]);
+    let mut trailing = valid.clone();
+    trailing.push(0);
+    let mut wrong_tag = valid.clone();
+    wrong_tag[0] = 0x31;
+    let mut long_length = vec![0x30, 0x81];
+    long_length.extend_from_slice(&valid[1..]);
+    let mut padded_r = vec![0x02, der_integer(r)[1] + 1, 0x00];
+    padded_r.extend_from_slice(&der_integer(r)[2..]);
+    let extra_zero = der_sequence(&[padded_r, der_integer(s)]);
+    // `n - 1` has the high bit set for all the curves, so its encoding needs a leading zero.
+    let negative_r = der_sequence(&[der_integer_without_zero(n_wide - 1), der_integer(s)]);
+
+    for (comment, flags, sig) in [
+        ("trailing garbage", &["InvalidEncoding"][..], trailing),
+        ("wrong sequence tag", &["InvalidEncoding"], wrong_tag),
+        ("long form length", &["BerEncodedSignature"], long_length),
+        (
+            "unnecessary leading zero",
+            &["BerEncodedSignature"],
+            extra_zero,
+        ),
+        ("missing leading zero", &["MissingZero"], negative_r),
+        (
+            "truncated",
+            &["InvalidEncoding"],
+            valid[..valid.len() - 1].to_vec(),
+        ),
+        ("empty", &["InvalidEncoding"], Vec::new()),
+        (
+            "only r",
+            &["InvalidEncoding"],
+            der_sequence(&[der_integer(r)]),
+        ),
+    ] {
+        group.push(tests.push(comment, flags, msg, &sig, "invalid"));
+    }
+
+    let mut groups = vec![ecdsa_group::<C>(&public, group)];
+
+    // A signature where `R.x >= n`, with the public key derived from it.
+    if let Some(big_r) = point_with_large_x::<C>() {
+        let (x, _y) = big_r.to_xy_u64().expect("the point is not the identity");
+        let r = x - n;
+        let s = scalar_from_label::<C>("s for large R.x");
+        let s_inv = invert_mod(s, n);
+        let u1 = mul_mod(z, s_inv, n);
+        let u2 = mul_mod(r, s_inv, n);
+        // `Q = (R - u1 * G) / u2`
+        let diff = *big_r.as_ref() - Point::<C>::GENERATOR.mul_u64(u1).as_ref();
+        let public = Point::from(diff).mul_u64(invert_mod(u2, n));
+
+        let group = vec![
+            tests.push(
+                "R.x >= n",
+                &["ArithmeticError"],
+                msg,
+                &der_signature(&[r.into(), s.into()]),
+                "valid",
+            ),
+            tests.push(
+                "R.x >= n, with r = R.x",
+                &["RangeCheck"],
+                msg,
+                &der_signature(&[x.into(), s.into()]),
+                "invalid",
+            ),
+        ];
+        groups.push(ecdsa_group::<C>(&public, group));
+    }
+
+    json!({
+        "algorithm": "ECDSA",
+        "schema": "ecdsa_verify_schema_v1.json",
+        "generatorVersion": env!("CARGO_PKG_VERSION"),
+        "numberOfTests": tests.count,
+        "header": [
+            format!("ECDSA verification test vectors for {} with SHA-256,", C::NAME),
+            "generated by the tiny-curve crate.",
+            "The hash is truncated to the bit size of the order.",
+        ],
+        "notes": notes(&[
+            ("ArithmeticError", "EDGE_CASE", "Edge cases of the curve arithmetic (points at infinity, R.x >= n)."),
+            ("BerEncodedSignature", "BER_ENCODING", "The signature uses BER instead of DER."),
+            ("InvalidEncoding", "CAN_OF_WORMS", "The signature is not a valid DER encoding."),
+            ("InvalidSignature", "AUTH_BYPASS", "r or s is 0."),
+            ("MissingZero", "LEGACY", "An integer is negative because of a missing leading zero."),
+            ("ModifiedInteger", "AUTH_BYPASS", "r or s is modified."),
+            ("RangeCheck", "CAN_OF_WORMS", "r or s is not in the range [1, n - 1]."),
+            ("SignatureMalleabilityBitcoin", "SIGNATURE_MALLEABILITY", "s is larger than n / 2."),
+            ("SmallRandS", "EDGE_CASE", "r and s are small."),
+            ("ValidSignature", "BASIC", "A valid signature."),
+        ]),
+        "testGroups": groups,
+    })
+}
+
+/// Returns the ECDH test vectors (an `EcdhEcpointTest` file) for the curve.
+pub fn ecdh_vectors<C>() -> Value
+where
+    C: WycheproofCurve,
+    AffinePoint<C>: FromEncodedPoint<C> + ToEncodedPoint<C>,
+    C::FieldBytesSize: ModulusSize,
+{
+    let n = C::ORDER_U64;
+    let mut tests = TestList::default();
+    let mut group = Vec::new();
+
+    let d = scalar_from_label::<C>("ecdh private key");
+    let peer = Point::<C>::GENERATOR.mul_u64(scalar_from_label::<C>("ecdh public key"));
+
+    for (comment, flags, d) in [
+        ("valid", &["Normal"][..], d),
+        ("private key 1", &["EdgeCasePrivateKey"], 1),
+        ("private key n - 1", &["EdgeCasePrivateKey"], n - 1),
+    ] {
+        group.push(tests.push_ecdh(
+            comment,
+            flags,
+            &encode_point(&peer, false),
+            d,
+            Some(&peer.mul_u64(d)),
+            "valid",
+        ));
+    }
+
+    group.push(tests.push_ecdh(
+        "compressed public key",
+        &["CompressedPublic"],
+        &encode_point(&peer, true),
+        d,
+        Some(&peer.mul_u64(d)),
+        "acceptable",
+    ));
+
+    if let Some(point) = point_with_large_x::<C>() {
+        group.push(tests.push_ecdh(
+            "public key with x >= n",
+            &["EdgeCasePublicKey"],
+            &encode_point(&point, false),
+            d,
+            Some(&point.mul_u64(d)),
+            "valid",
+        ));
+    }
+
+    let uncompressed = encode_point(&peer, false);
+    let coordinate_size = (uncompressed.len() - 1) / 2;
+
+    let mut not_on_curve = uncompressed.clone();
+    *not_on_curve.last_mut().expect("the encoding is not empty") ^= 1;
+
+    let mut x_out_of_range = uncompressed.clone();
+    let (x, _y) = peer.to_xy_u64().expect("the point is not the identity");
+    if let Some(x_plus_p) = x.checked_add(C::FIELD_MODULUS_U64) {
+        x_out_of_range[coordinate_size - 7..coordinate_size + 1]
+            .copy_from_slice(&x_plus_p.to_be_bytes());
+    } else {
+        // Set a byte above the 8 low ones, making the value exceed the modulus
+        x_out_of_range[coordinate_size - 8] = 1;
+    }
+
+    let mut wrong_tag = uncompressed.clone();
+    wrong_tag[0] = 0x05;
+
+    let mut invalid = vec![
+        ("point at infinity", &["InvalidPublic"][..], vec![0x00]),
+        ("point not on curve", &["InvalidPublic"], not_on_curve),
+        ("x out of range", &["InvalidPublic"], x_out_of_range),
+        ("wrong tag", &["InvalidEncoding"], wrong_tag),
+        (
+            "truncated",
+            &["InvalidEncoding"],
+            uncompressed[..uncompressed.len() - 1].to_vec(),
+        ),
+        ("empty", &["InvalidEncoding"], Vec::new()),
+    ];
+    if let Some(x) = x_without_point::<C>() {
+        let mut compressed = encode_point(&peer, true);
+        compressed[coordinate_size - 7..].copy_from_slice(&x.to_be_bytes());
+        invalid.push((
+            "compressed x without a point",
+            &["InvalidPublic"],
+            compressed,
+        ));
+    }
+
+    for (comment, flags, public) in invalid {
+        group.push(tests.push_ecdh(comment, flags, &public, d, None, "invalid"));
+    }
+
+    json!({
+        "algorithm": "ECDH",
+        "schema": "ecdh_ecpoint_test_schema_v1.json",
+        "generatorVersion": env!("CARGO_PKG_VERSION"),
+        "numberOfTests": tests.count,
+        "header": [
+            format!("ECDH test vectors for {} with SEC1-encoded public points,", C::NAME),
+            "generated by the tiny-curve crate.",
+            "The shared secrets are the 24-byte x-coordinates of the shared points.",
+        ],
+        "notes": notes(&[
+            ("CompressedPublic", "EDGE_CASE", "The public key is compressed."),
+            ("EdgeCasePrivateKey", "EDGE_CASE", "The private key is an edge case value."),
+            ("EdgeCasePublicKey", "EDGE_CASE", "The public key has an x-coordinate >= n."),
+            ("InvalidEncoding", "CAN_OF_WORMS", "The public key is not a valid SEC1 encoding."),
+            ("InvalidPublic", "CAN_OF_WORMS", "The public key is not a valid point."),
+            ("Normal", "BASIC", "A valid key exchange."),
+        ]),
+        "testGroups": [{
+            "type": "EcdhEcpointTest",
+            "curve": C::NAME,
+            "encoding": "ecpoint",
+            "tests": group,
+        }],
+    })
+}
+
+#[derive(Default)]
+struct TestList {
+    count: usize,
+}
+
+impl TestList {
+    fn next_id(&mut self) -> usize {
+        self.count += 1;
+        self.count
+    }
+
+    fn push(
+        &mut self,
+        comment: &str,
+        flags: &[&str],
+        msg: &[u8],
+        sig: &[u8],
+        result: &str,
+    ) -> Value {
+        json!({
+            "tcId": self.next_id(),
+            "comment": comment,
+            "flags": flags,
+            "msg": hex(msg),
+            "sig": hex(sig),
+            "result": result,
+        })
+    }
+
+    fn push_ecdh<C>(
+        &mut self,
+        comment: &str,
+        flags: &[&str],
+        public: &[u8],
+        private: u64,
+        shared: Option<&Point<C>>,
+        result: &str,
+    ) -> Value
+    where
+        C: PrimeCurveParams,
+        AffinePoint<C>: ToEncodedPoint<C>,
+        C::FieldBytesSize: ModulusSize,
+    {
+        let shared = shared.map_or_else(String::new, |point| {
+            hex(point
+                .as_ref()
+                .to_affine()
+                .to_encoded_point(false)
+                .x()
+                .expect("the shared point is not the identity"))
+        });
+        json!({
+            "tcId": self.next_id(),
+            "comment": comment,
+            "flags": flags,
+            "public": hex(public),
+            "private": hex(&der_integer(private.into())[2..]),
+            "shared": shared,
+            "result": result,
+        })
+    }
+}
+
+fn notes(notes: &[(&str, &str, &str)]) -> Value {
+    Value::Object(
+        notes
+            .iter()
+            .map(|(flag, bug_type, description)| {
+                (
+                    String::from(*flag),
+                    json!({ "bugType": bug_type, "description": description }),
+                )
+            })
+            .collect(),
+    )
+}
+
+fn ecdsa_group<C>(public: &Point<C>, tests: Vec<Value>) -> Value
+where
+    C: WycheproofCurve,
+    AffinePoint<C>: FromEncodedPoint<C> + ToEncodedPoint<C>,
+    C::FieldBytesSize: ModulusSize,
+{
+    let (x, y) = public
+        .to_xy_u64()
+        .expect("the public key is not the identity");
+    let public_key = PublicKey::<C>::from_affine(public.as_ref().to_affine())
+        .expect("the public key is not the identity");
+    json!({
+        "type": "EcdsaVerify",
+        "publicKey": {
+            "type": "EcPublicKey",
+            "curve": C::NAME,
+            "keySize": 64 - C::ORDER_U64.leading_zeros(),
+            "uncompressed": hex(&encode_point(public, false)),
+            "wx": hex(&der_integer(x.into())[2..]),
+            "wy": hex(&der_integer(y.into())[2..]),
+        },
+        "publicKeyDer": hex(public_key.to_public_key_der().expect("the key can be encoded").as_bytes()),
+        "publicKeyPem": public_key
+            .to_public_key_pem(LineEnding::LF)
+            .expect("the key can be encoded"),
+        "sha": "SHA-256",
+        "tests": tests,
+    })
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{byte:02x}")).collect()
+}
+
+fn encode_point<C>(point: &Point<C>, compress: bool) -> Vec<u8>
+where
+    C: PrimeCurveParams,
+    AffinePoint<C>: ToEncodedPoint<C>,
+    C::FieldBytesSize: ModulusSize,
+{
+    point
+        .as_ref()
+        .to_affine()
+        .to_encoded_point(compress)
+        .as_bytes()
+        .to_vec()
+}
+
+/// Returns a point with `n < x < p`, if there is one (`x = n` would make `r = 0`).
+fn point_with_large_x<C>() -> Option<Point<C>>
+where
+    C: WycheproofCurve,
+    AffinePoint<C>: FromEncodedPoint<C> + ToEncodedPoint<C>,
+    C::FieldBytesSize: ModulusSize,
+{
+    (C::ORDER_U64 + 1..C::FIELD_MODULUS_U64).find_map(|x| decompress::<C>(x))
+}
+
+/// Returns an x-coordinate that does not correspond to a point on the curve.
+fn x_without_point<C>() -> Option<u64>
+where
+    C: WycheproofCurve,
+    AffinePoint<C>: FromEncodedPoint<C> + ToEncodedPoint<C>,
+    C::FieldBytesSize: ModulusSize,
+{
+    (1..C::FIELD_MODULUS_U64).find(|x| decompress::<C>(*x).is_none())
+}
+
+fn decompress<C>(x: u64) -> Option<Point<C>>
+where
+    C: PrimeCurveParams,
+    AffinePoint<C>: FromEncodedPoint<C>,
+    C::FieldBytesSize: ModulusSize,
+{
+    let mut x_bytes = FieldBytes::<C>::default();
+    let x_len = x_bytes.len();
+    x_bytes[x_len - 8..].copy_from_slice(&x.to_be_bytes());
+    let encoded = EncodedPoint::<C>::from_affine_coordinates(&x_bytes, &x_bytes, true);
+    Option::<AffinePoint<C>>::from(AffinePoint::<C>::from_encoded_point(&encoded)).map(Point::from)
+}
+
+// Integer ECDSA over the curve order (all the orders fit in `u64`).
+
+fn add_mod(x: u64, y: u64, n: u64) -> u64 {
+    ((u128::from(x) + u128::from(y)) % u128::from(n)) as u64
+}
+
+fn mul_mod(x: u64, y: u64, n: u64) -> u64 {
+    (u128::from(x) * u128::from(y) % u128::from(n)) as u64
+}
+
+fn invert_mod(x: u64, n: u64) -> u64 {
+    let mut result = 1;
+    let mut base = x;
+    let mut exp = n - 2;
+    while exp > 0 {
+        if exp & 1 == 1 {
+            result = mul_mod(result, base, n);
+        }
+        base = mul_mod(base, base, n);
+        exp >>= 1;
+    }
+    result
+}
+
+fn hash_to_scalar<C: WycheproofCurve>(msg: &[u8]) -> u64 {
+    let bytes = (64 - C::ORDER_U64.leading_zeros()).div_ceil(8) as usize;
+    let mut buffer = [0u8; 8];
+    buffer[8 - bytes..].copy_from_slice(&Sha256::digest(msg)[..bytes]);
+    u64::from_be_bytes(buffer) % C::ORDER_U64
+}
+
+fn scalar_from_label<C: WycheproofCurve>(label: &str) -> u64 {
+    let digest = Sha256::digest(label.as_bytes());
+    let value = u64::from_be_bytes(
+        digest[..8]
+            .try_into()
+            .expect("slice has the correct length"),
+    );
+    value % (C::ORDER_U64 - 1) + 1
+}
+
+fn sign<C>(d: u64, msg: &[u8], k: u64) -> (u64, u64)
+where
+    C: WycheproofCurve,
+    AffinePoint<C>: ToEncodedPoint<C>,
+    C::FieldBytesSize: ModulusSize,
+{
+    let n = C::ORDER_U64;
+    let (x, _y) = Point::<C>::GENERATOR
+        .mul_u64(k)
+        .to_xy_u64()
+        .expect("k is nonzero");
+    let r = x % n;
+    let s = mul_mod(
+        invert_mod(k, n),
+        add_mod(hash_to_scalar::<C>(msg), mul_mod(r, d, n), n),
+        n,
+    );
+    // Does not happen for the fixed labels used here.
+    assert!(r != 0 && s != 0, "degenerate signature");
+    (r, s)
+}
+
+// DER encoding
+
+fn der_length(length: usize) -> Vec<u8> {
+    if length < 0x80 {
+        vec![length as u8]
+    } else {
+        vec![0x81, length as u8]
+    }
+}
+
+fn der_integer(value: u128) -> Vec<u8> {
+    let bytes = value.to_be_bytes();
+    let start = bytes
+        .iter()
+        .position(|byte| *byte != 0)
+        .unwrap_or(bytes.len() - 1);
+    let mut contents = Vec::new();
+    if bytes[start] & 0x80 != 0 {
+        contents.push(0);
+    }
+    contents.extend_from_slice(&bytes[start..]);
+    let mut result = vec![0x02];
+    result.extend(der_length(contents.len()));
+    result.extend(contents);
+    result
+}
+
+// Encodes an integer with the high bit set without the leading zero (so that it reads as negative).
+fn der_integer_without_zero(value: u128) -> Vec<u8> {
+    let mut encoded = der_integer(value);
+    debug_assert!(encoded[2] == 0);
+    encoded.remove(2);
+    encoded[1] -= 1;
+    encoded
+}
+
+fn der_sequence(items: &[Vec<u8>]) -> Vec<u8> {
+    let contents = items.concat();
+    let mut result = vec![0x30];
+    result.extend(der_length(contents.len()));
+    result.extend(contents);
+    result
+}
+
+fn der_signature(integers: &[u128]) -> Vec<u8> {
+    der_sequence(
+        &integers
+            .iter()
+            .map(|value| der_integer(*value))
+            .collect::<Vec<_>>(),
+    )
+}
+
+#[cfg(all(test, feature = "ecdsa"))]
+mod tests {
+    use ecdsa::{signature::Verifier, Signature, VerifyingKey};
+    use primeorder::elliptic_curve::{
+        pkcs8::DecodePublicKey, sec1::ToEncodedPoint, CurveArithmetic, PublicKey,
+    };
+    use serde_json::Value;
+
+    use super::{ecdh_vectors, ecdsa_verify_vectors, test_vector_files};
+    use crate::{TinyCurve16, TinyCurve32, TinyCurve64};
+
+    fn from_hex(value: &Value) -> Vec<u8> {
+        let s = value.as_str().unwrap();
+        (0..s.len())
+            .step_by(2)
+            .map(|i| u8::from_str_radix(&s[i..i + 2], 16).unwrap())
+            .collect()
+    }
+
+    fn check_ids(vectors: &Value) {
+        let ids = vectors["testGroups"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .flat_map(|group| group["tests"].as_array().unwrap())
+            .map(|test| test["tcId"].as_u64().unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            ids,
+            (1..=vectors["numberOfTests"].as_u64().unwrap()).collect::<Vec<_>>()
+        );
+    }
+
+    // Checks the vectors against the implementations in `ecdsa` and `elliptic_curve`.
+    macro_rules! check_vectors {
+        ($curve:ident) => {{
+            type Scalar = <$curve as CurveArithmetic>::Scalar;
+
+            let vectors = ecdsa_verify_vectors::<$curve>();
+            check_ids(&vectors);
+            for group in vectors["testGroups"].as_array().unwrap() {
+                let key = &group["publicKey"];
+                let vk = VerifyingKey::<$curve>::from_sec1_bytes(&from_hex(&key["uncompressed"]))
+                    .unwrap();
+                assert_eq!(
+                    VerifyingKey::<$curve>::from_public_key_der(&from_hex(&group["publicKeyDer"]))
+                        .unwrap(),
+                    vk
+                );
+                assert_eq!(
+                    VerifyingKey::<$curve>::from_public_key_pem(
+                        group["publicKeyPem"].as_str().unwrap()
+                    )
+                    .unwrap(),
+                    vk
+                );
+
+                for test in group["tests"].as_array().unwrap() {
+                    let msg = from_hex(&test["msg"]);
+                    let verified = Signature::<$curve>::from_der(&from_hex(&test["sig"]))
+                        .map_or(false, |sig| vk.verify(&msg, &sig).is_ok());
+                    assert_eq!(
+                        verified,
+                        test["result"] == "valid",
+                        "test {}: {}",
+                        test["tcId"],
+                        test["comment"]
+                    );
+                }
+            }
+
+            let vectors = ecdh_vectors::<$curve>();
+            check_ids(&vectors);
+            for test in vectors["testGroups"][0]["tests"].as_array().unwrap() {
+                let private = from_hex(&test["private"])
+                    .iter()
+                    .fold(0u64, |acc, byte| (acc << 8) | u64::from(*byte));
+                let shared = PublicKey::<$curve>::from_sec1_bytes(&from_hex(&test["public"]))
+                    .ok()
+                    .map(|pk| {
+                        (pk.to_projective() * Scalar::from(private))
+                            .to_affine()
+                            .to_encoded_point(false)
+                            .x()
+                            .unwrap()
+                            .to_vec()
+                    });
+                match test["result"].as_str().unwrap() {
+                    "valid" | "acceptable" => {
+                        assert_eq!(
+                            shared,
+                            Some(from_hex(&test["shared"])),
+                            "{}",
+                            test["comment"]
+                        )
+                    }
+                    _ => assert_eq!(shared, None, "{}", test["comment"]),
+                }
+            }
+        }};
+    }
+
+    #[test]
+    fn vectors() {
+        check_vectors!(TinyCurve16);
+        check_vectors!(TinyCurve32);
+        check_vectors!(TinyCurve64);
+    }
+
+    #[test]
+    fn files() {
+        let files = test_vector_files();
+        assert_eq!(files.len(), 6);
+        assert_eq!(files[0].0, "ecdsa_tinycurve16_sha256_test.json");
+        assert_eq!(files[1].0, "ecdh_tinycurve16_ecpoint_test.json");
+        assert_eq!(files[0].1["schema"], "ecdsa_verify_schema_v1.json");
+        assert_eq!(files[1].1["schema"], "ecdh_ecpoint_test_schema_v1.json");
+    }
+}
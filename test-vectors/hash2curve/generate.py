@@ -0,0 +1,262 @@
+#!/usr/bin/env python3
+"""
+Generates hash-to-curve test vectors for the curves from this crate.
+
+This is an independent straightforward implementation of RFC 9380
+(`expand_message_xmd` with SHA-256, `hash_to_field`, and the simplified SWU map
+from Section 6.6.2), used to cross-check the Rust implementation.
+
+Usage: `python3 generate.py` (writes the JSON files next to this script).
+"""
+
+import hashlib
+import json
+from pathlib import Path
+
+CURVES = {
+    # name: (field modulus, order, a, b)
+    "TinyCurve16": (0xFFA7, 0xFE93, -3, 7),
+    "TinyCurve32": (0xFFFFFF67, 0xFFFF0F07, -3, 8),
+    "TinyCurve64": (0xFFFFFFFFFFFFFC7F, 0xFFFFFFFF1A0A85DF, -3, 6),
+}
+
+# The security parameter used for `hash_to_field`.
+K = 128
+
+MESSAGES = [
+    b"",
+    b"abc",
+    b"abcdef0123456789",
+    b"q128_" + b"q" * 128,
+    b"a512_" + b"a" * 512,
+]
+
+
+def bit_length_bytes(p):
+    return (p.bit_length() + K + 7) // 8
+
+
+def expand_message_xmd(msg, dst, len_in_bytes):
+    b_in_bytes = 32
+    s_in_bytes = 64
+    ell = (len_in_bytes + b_in_bytes - 1) // b_in_bytes
+    assert ell <= 255 and len_in_bytes <= 65535 and len(dst) <= 255
+    dst_prime = dst + bytes([len(dst)])
+    z_pad = bytes(s_in_bytes)
+    l_i_b_str = len_in_bytes.to_bytes(2, "big")
+    msg_prime = z_pad + msg + l_i_b_str + bytes([0]) + dst_prime
+    b_0 = hashlib.sha256(msg_prime).digest()
+    b_vals = [hashlib.sha256(b_0 + bytes([1]) + dst_prime).digest()]
+    for i in range(2, ell + 1):
+        prev = bytes(x ^ y for x, y in zip(b_0, b_vals[-1]))
+        b_vals.append(hashlib.sha256(prev + bytes([i]) + dst_prime).digest())
+    return b"".join(b_vals)[:len_in_bytes]
+
+
+def hash_to_field(msg, dst, count, p):
+    length = bit_length_bytes(p)
+    uniform_bytes = expand_message_xmd(msg, dst, count * length)
+    return [
+        int.from_bytes(uniform_bytes[length * i : length * (i + 1)], "big") % p
+        for i in range(count)
+    ]
+
+
+def is_square(x, p):
+    return x % p == 0 or pow(x, (p - 1) // 2, p) == 1
+
+
+def sqrt(x, p):
+    assert p % 4 == 3
+    root = pow(x, (p + 1) // 4, p)
+    assert root * root % p == x % p
+    return root
+
+
+def inv0(x, p):
+    return pow(x, p - 2, p)
+
+
+def sgn0(x):
+    return x % 2
+
+
+def find_z_sswu(p, a, b):
+    """RFC 9380, Appendix H.2."""
+
+    def g(x):
+        return (x**3 + a * x + b) % p
+
+    ctr = 1
+    while True:
+        for z_cand in (ctr, -ctr):
+            z = z_cand % p
+            if is_square(z, p):
+                continue
+            if z == p - 1:
+                continue
+            # `g(x) - z` is a cubic, so it is irreducible iff it has no roots.
+            if not cubic_has_no_roots(a % p, (b - z) % p, p):
+                continue
+            if is_square(g(b * pow(z * a, -1, p) % p), p):
+                return z_cand
+        ctr += 1
+
+
+def poly_mulmod(f, g, m, p):
+    # Polynomials are lists of coefficients, lowest degree first; `m` is monic.
+    result = [0] * (len(f) + len(g) - 1)
+    for i, fi in enumerate(f):
+        for j, gj in enumerate(g):
+            result[i + j] = (result[i + j] + fi * gj) % p
+    return poly_mod(result, m, p)
+
+
+def poly_mod(f, m, p):
+    f = list(f)
+    while len(f) >= len(m):
+        coeff = f[-1]
+        shift = len(f) - len(m)
+        for i, mi in enumerate(m):
+            f[shift + i] = (f[shift + i] - coeff * mi) % p
+        f.pop()
+    while f and f[-1] == 0:
+        f.pop()
+    return f
+
+
+def poly_gcd(f, g, p):
+    while g:
+        inv = pow(g[-1], -1, p)
+        g = [c * inv % p for c in g]
+        f, g = g, poly_mod(f, g, p)
+    return f
+
+
+def cubic_has_no_roots(a, b, p):
+    """Checks that `x^3 + a * x + b` has no roots, that is `gcd(x^p - x, x^3 + a * x + b) == 1`."""
+    m = [b, a, 0, 1]
+    # Compute `x^p mod m` by square-and-multiply.
+    result = [1]
+    base = [0, 1]
+    e = p
+    while e:
+        if e & 1:
+            result = poly_mulmod(result, base, m, p)
+        base = poly_mulmod(base, base, m, p)
+        e >>= 1
+    # `x^p - x`
+    diff = result + [0] * (2 - len(result)) if len(result) < 2 else list(result)
+    diff[1] = (diff[1] - 1) % p
+    while diff and diff[-1] == 0:
+        diff.pop()
+    return len(poly_gcd(m, diff, p)) == 1
+
+
+def map_to_curve_simple_swu(u, p, a, b, z):
+    """RFC 9380, Section 6.6.2 (the straightforward version)."""
+    a %= p
+    b %= p
+    z %= p
+    tv1 = inv0(z * z * pow(u, 4, p) + z * u * u, p)
+    x1 = (-b * inv0(a, p) * (1 + tv1)) % p
+    if tv1 == 0:
+        x1 = b * inv0(z * a, p) % p
+    gx1 = (x1**3 + a * x1 + b) % p
+    x2 = z * u * u * x1 % p
+    gx2 = (x2**3 + a * x2 + b) % p
+    if is_square(gx1, p):
+        x, y = x1, sqrt(gx1, p)
+    else:
+        x, y = x2, sqrt(gx2, p)
+    if sgn0(u) != sgn0(y):
+        y = (-y) % p
+    return x, y
+
+
+def point_add(p1, p2, p, a):
+    if p1 is None:
+        return p2
+    if p2 is None:
+        return p1
+    x1, y1 = p1
+    x2, y2 = p2
+    if x1 == x2 and (y1 + y2) % p == 0:
+        return None
+    if p1 == p2:
+        lam = (3 * x1 * x1 + a) * pow(2 * y1, -1, p) % p
+    else:
+        lam = (y2 - y1) * pow(x2 - x1, -1, p) % p
+    x3 = (lam * lam - x1 - x2) % p
+    y3 = (lam * (x1 - x3) - y1) % p
+    return x3, y3
+
+
+def to_hex(value, p):
+    return "0x" + format(value, "0{}x".format((p.bit_length() + 7) // 8 * 2))
+
+
+def point_json(point, p):
+    return {"x": to_hex(point[0], p), "y": to_hex(point[1], p)}
+
+
+def generate(name, p, n, a, b):
+    z = find_z_sswu(p, a, b)
+    files = {}
+    for variant in ("RO", "NU"):
+        suite_id = "{}_XMD:SHA-256_SSWU_{}_".format(name, variant)
+        dst = ("QUUX-V01-CS02-with-" + suite_id).encode()
+        vectors = []
+        for msg in MESSAGES:
+            count = 2 if variant == "RO" else 1
+            us = hash_to_field(msg, dst, count, p)
+            qs = [map_to_curve_simple_swu(u, p, a, b, z) for u in us]
+            point = None
+            for q in qs:
+                point = point_add(point, q, p, a)
+            vector = {
+                "msg": msg.decode(),
+                "P": point_json(point, p),
+                "u": [to_hex(u, p) for u in us],
+            }
+            for i, q in enumerate(qs):
+                vector["Q{}".format(i)] = point_json(q, p)
+            vectors.append(vector)
+        files[suite_id] = {
+            "ciphersuite": suite_id,
+            "dst": dst.decode(),
+            "field": {"p": to_hex(p, p), "m": 1},
+            "curve": "{} (y^2 = x^3 - 3x + {})".format(name, b),
+            "map": {"name": "SSWU", "Z": str(z)},
+            "k": K,
+            "L": bit_length_bytes(p),
+            "expand": "XMD",
+            "hash": "sha256",
+            "randomOracle": variant == "RO",
+            "vectors": vectors,
+        }
+
+    dst = ("QUUX-V01-CS02-with-" + name + "_XMD:SHA-256_SSWU_RO_scalar").encode()
+    files[name + "_hash_to_scalar"] = {
+        "dst": dst.decode(),
+        "order": to_hex(n, n),
+        "vectors": [
+            {"msg": msg.decode(), "scalar": to_hex(hash_to_field(msg, dst, 1, n)[0], n)}
+            for msg in MESSAGES
+        ],
+    }
+    return z, files
+
+
+def main():
+    directory = Path(__file__).parent
+    for name, (p, n, a, b) in CURVES.items():
+        z, files = generate(name, p, n, a, b)
+        print("{}: Z = {}, sqrt(-Z) = {}".format(name, z, sqrt(-z % p, p)))
+        for file_name, contents in files.items():
+            path = directory / (file_name.replace(":", "-") + ".json")
+            path.write_text(json.dumps(contents, indent=2) + "\n")
+
+
+if __name__ == "__main__":
+    main()
@@ -0,0 +1,344 @@
+"""
+Generates known-answer tests for the tiny curves: RFC 6979 deterministic ECDSA signatures,
+SEC1/PKCS#8/SPKI key encodings and BIP32 derivations.
+
+This is an independent implementation following the specifications and the conventions
+the crate uses (documented inline); the vectors are checked byte-for-byte in `src/kat.rs`.
+
+Usage: `python3 generate.py` (writes the JSON files next to this script).
+"""
+
+import hashlib
+import hmac
+import json
+from pathlib import Path
+
+CURVES = {
+    # name: (field modulus, order, b, generator, hash bytes, OID)
+    "TinyCurve16": (
+        0xFFA7,
+        0xFE93,
+        7,
+        (23947, 53757),
+        2,
+        "1.3.6.1.4.1.202767.1",
+    ),
+    "TinyCurve32": (
+        0xFFFFFF67,
+        0xFFFF0F07,
+        8,
+        (4274000713, 443355223),
+        4,
+        "1.3.6.1.4.1.202767.2",
+    ),
+    "TinyCurve64": (
+        0xFFFFFFFFFFFFFC7F,
+        0xFFFFFFFF1A0A85DF,
+        6,
+        (11619086278950426528, 2765382488766937725),
+        8,
+        "1.3.6.1.4.1.202767.3",
+    ),
+}
+
+# All the curves have `a = -3`.
+A = -3
+
+# The size of the field element representation (`FieldBytes`) in bytes.
+REPR_SIZE = 24
+
+# `id-ecPublicKey`
+EC_PUBLIC_KEY_OID = "1.2.840.10045.2.1"
+
+MESSAGES = [b"", b"sample", b"test"]
+
+# Non-hardened and hardened indices
+BIP32_PATH = [0, 1, 0x80000000, 2, 0x80000001, 1000000000]
+
+# How many child numbers to try when searching for invalid children
+BIP32_SEARCH_LIMIT = 1 << 18
+
+
+# Curve arithmetic (affine, `None` is the point at infinity)
+
+
+def point_add(p1, p2, p):
+    if p1 is None:
+        return p2
+    if p2 is None:
+        return p1
+    x1, y1 = p1
+    x2, y2 = p2
+    if x1 == x2 and (y1 + y2) % p == 0:
+        return None
+    if p1 == p2:
+        lam = (3 * x1 * x1 + A) * pow(2 * y1, -1, p) % p
+    else:
+        lam = (y2 - y1) * pow(x2 - x1, -1, p) % p
+    x3 = (lam * lam - x1 - x2) % p
+    return (x3, (lam * (x1 - x3) - y1) % p)
+
+
+def point_mul(k, point, p):
+    result = None
+    while k > 0:
+        if k & 1:
+            result = point_add(result, point, p)
+        point = point_add(point, point, p)
+        k >>= 1
+    return result
+
+
+# Encodings
+
+
+def to_bytes(value, length=REPR_SIZE):
+    return value.to_bytes(length, "big")
+
+
+def encode_point(point, compressed):
+    x, y = point
+    if compressed:
+        return bytes([2 + (y & 1)]) + to_bytes(x)
+    return b"\x04" + to_bytes(x) + to_bytes(y)
+
+
+def der_tlv(tag, contents):
+    length = len(contents)
+    if length < 0x80:
+        encoded_length = bytes([length])
+    else:
+        length_bytes = length.to_bytes((length.bit_length() + 7) // 8, "big")
+        encoded_length = bytes([0x80 | len(length_bytes)]) + length_bytes
+    return bytes([tag]) + encoded_length + contents
+
+
+def der_integer(value):
+    contents = value.to_bytes(max(1, (value.bit_length() + 8) // 8), "big")
+    return der_tlv(0x02, contents)
+
+
+def der_oid(oid):
+    arcs = [int(arc) for arc in oid.split(".")]
+    contents = bytearray([40 * arcs[0] + arcs[1]])
+    for arc in arcs[2:]:
+        chunk = [arc & 0x7F]
+        arc >>= 7
+        while arc:
+            chunk.append(0x80 | (arc & 0x7F))
+            arc >>= 7
+        contents.extend(reversed(chunk))
+    return der_tlv(0x06, bytes(contents))
+
+
+def der_sequence(*items):
+    return der_tlv(0x30, b"".join(items))
+
+
+def der_bit_string(data):
+    return der_tlv(0x03, b"\x00" + data)
+
+
+def der_octet_string(data):
+    return der_tlv(0x04, data)
+
+
+def sec1_der(d, public):
+    # `ECPrivateKey` (RFC 5915) without the optional parameters, as `elliptic-curve` does.
+    return der_sequence(
+        der_integer(1),
+        der_octet_string(to_bytes(d)),
+        der_tlv(0xA1, der_bit_string(encode_point(public, False))),
+    )
+
+
+def algorithm_identifier(oid):
+    return der_sequence(der_oid(EC_PUBLIC_KEY_OID), der_oid(oid))
+
+
+def pkcs8_der(d, public, oid):
+    return der_sequence(
+        der_integer(0), algorithm_identifier(oid), der_octet_string(sec1_der(d, public))
+    )
+
+
+def spki_der(public, oid):
+    return der_sequence(algorithm_identifier(oid), der_bit_string(encode_point(public, False)))
+
+
+# ECDSA
+
+
+def tiny_hash(msg, hash_bytes):
+    # The leftmost `hash_bytes` of SHA-256, padded with zeros on the left to `REPR_SIZE`.
+    return bytes(REPR_SIZE - hash_bytes) + hashlib.sha256(msg).digest()[:hash_bytes]
+
+
+def tiny_hmac(key, msg, hash_bytes):
+    block_size = 64
+    key = key + bytes(block_size - len(key))
+    inner = tiny_hash(bytes(b ^ 0x36 for b in key) + msg, hash_bytes)
+    return tiny_hash(bytes(b ^ 0x5C for b in key) + inner, hash_bytes)
+
+
+def rfc6979_k(d, h, n, hash_bytes):
+    # RFC 6979, Section 3.2, with `TinyHash` as the hash function and `REPR_SIZE`-byte
+    # integer encodings. Note that, following `ecdsa`, the hash is used as is,
+    # without the reduction modulo `n` from `bits2octets`.
+    x = to_bytes(d)
+    v = b"\x01" * REPR_SIZE
+    k = bytes(REPR_SIZE)
+    k = tiny_hmac(k, v + b"\x00" + x + h, hash_bytes)
+    v = tiny_hmac(k, v, hash_bytes)
+    k = tiny_hmac(k, v + b"\x01" + x + h, hash_bytes)
+    v = tiny_hmac(k, v, hash_bytes)
+    while True:
+        v = tiny_hmac(k, v, hash_bytes)
+        candidate = int.from_bytes(v, "big")
+        if 0 < candidate < n:
+            return candidate
+        k = tiny_hmac(k, v + b"\x00", hash_bytes)
+        v = tiny_hmac(k, v, hash_bytes)
+
+
+def sign(d, msg, p, n, g, hash_bytes):
+    h = tiny_hash(msg, hash_bytes)
+    z = int.from_bytes(h, "big") % n
+    k = rfc6979_k(d, h, n, hash_bytes)
+    x, y = point_mul(k, g, p)
+    r = x % n
+    s = pow(k, -1, n) * (z + r * d) % n
+    assert r != 0 and s != 0
+    recovery_id = (y & 1) | (2 if x >= n else 0)
+    return k, r, s, recovery_id
+
+
+# BIP32
+
+
+def bip32_private_bytes(d):
+    return to_bytes(d, 32)
+
+
+def bip32_public_bytes(public):
+    return encode_point(public, True).rjust(33, b"\x00")
+
+
+def bip32_tweak(il, n):
+    # The leftmost bytes of IL, as many as there are in the order; `None` if out of range.
+    tweak = int.from_bytes(il[: (n.bit_length() + 7) // 8], "big")
+    return tweak if tweak < n else None
+
+
+def bip32_hmac(d, public, chain_code, index):
+    if index >= 0x80000000:
+        data = b"\x00" + bip32_private_bytes(d)
+    else:
+        data = bip32_public_bytes(public)
+    digest = hmac.new(chain_code, data + index.to_bytes(4, "big"), hashlib.sha512).digest()
+    return digest[:32], digest[32:]
+
+
+def bip32_derive(d, chain_code, index, p, n, g):
+    # Returns `None` if the child is invalid (BIP32 says to proceed with the next index).
+    il, ir = bip32_hmac(d, point_mul(d, g, p), chain_code, index)
+    tweak = bip32_tweak(il, n)
+    if tweak is None or (d + tweak) % n == 0:
+        return None
+    return (d + tweak) % n, ir
+
+
+def bip32_invalid_children(d, chain_code, p, n, g, start):
+    # The first invalid child number starting from `start`, if it can be found quickly.
+    public = point_mul(d, g, p)
+    for index in range(start, start + BIP32_SEARCH_LIMIT):
+        il, _ir = bip32_hmac(d, public, chain_code, index)
+        tweak = bip32_tweak(il, n)
+        if tweak is None or (d + tweak) % n == 0:
+            return index
+    return None
+
+
+def fixed_scalar(label, n):
+    return int.from_bytes(hashlib.sha256(label.encode()).digest(), "big") % (n - 1) + 1
+
+
+def generate(name, p, n, b, g, hash_bytes, oid):
+    keys = [1, fixed_scalar(name + " key", n), n - 1]
+
+    encodings = []
+    for d in keys:
+        public = point_mul(d, g, p)
+        encodings.append(
+            {
+                "secret_key": to_bytes(d).hex(),
+                "public_key_compressed": encode_point(public, True).hex(),
+                "public_key_uncompressed": encode_point(public, False).hex(),
+                "sec1_der": sec1_der(d, public).hex(),
+                "pkcs8_der": pkcs8_der(d, public, oid).hex(),
+                "spki_der": spki_der(public, oid).hex(),
+            }
+        )
+
+    signatures = []
+    for d in keys:
+        for msg in MESSAGES:
+            k, r, s, recovery_id = sign(d, msg, p, n, g, hash_bytes)
+            signatures.append(
+                {
+                    "secret_key": to_bytes(d).hex(),
+                    "msg": msg.decode(),
+                    "k": to_bytes(k).hex(),
+                    "r": to_bytes(r).hex(),
+                    "s": to_bytes(s).hex(),
+                    "recovery_id": recovery_id,
+                    "signature": (to_bytes(r) + to_bytes(s)).hex(),
+                    "signature_der": der_sequence(der_integer(r), der_integer(s)).hex(),
+                }
+            )
+
+    d = fixed_scalar(name + " bip32 key", n)
+    chain_code = hashlib.sha256((name + " bip32 chain code").encode()).digest()
+    bip32 = {
+        "private_key": bip32_private_bytes(d).hex(),
+        "public_key": bip32_public_bytes(point_mul(d, g, p)).hex(),
+        "chain_code": chain_code.hex(),
+        "invalid_child_numbers": [],
+        "path": [],
+    }
+    invalid = [
+        bip32_invalid_children(d, chain_code, p, n, g, start) for start in [0, 0x80000000]
+    ]
+    bip32["invalid_child_numbers"] = [index for index in invalid if index is not None]
+    for index in BIP32_PATH:
+        d, chain_code = bip32_derive(d, chain_code, index, p, n, g)
+        bip32["path"].append(
+            {
+                "child_number": index,
+                "private_key": bip32_private_bytes(d).hex(),
+                "public_key": bip32_public_bytes(point_mul(d, g, p)).hex(),
+                "chain_code": chain_code.hex(),
+            }
+        )
+
+    return {
+        "curve": "{} (y^2 = x^3 - 3x + {})".format(name, b),
+        "p": hex(p),
+        "n": hex(n),
+        "oid": oid,
+        "hash": "SHA-256, leftmost {} bytes".format(hash_bytes),
+        "encodings": encodings,
+        "ecdsa_rfc6979": signatures,
+        "bip32": bip32,
+    }
+
+
+def main():
+    directory = Path(__file__).parent
+    for name, params in CURVES.items():
+        path = directory / (name + ".json")
+        path.write_text(json.dumps(generate(name, *params), indent=2) + "\n")
+
+
+if __name__ == "__main__":
+    main()